@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use symbols::EntityFilter;
+
+#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tea_kind")]
+pub enum Tea {
+    #[sea_orm(string_value = "E")]
+    EverydayTea,
+    #[sea_orm(string_value = "B")]
+    BreakfastTea,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "drinks")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub name: String,
+    pub kind: Tea,
+    pub fallback: Option<Tea>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl EntityFilter for Entity {}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Column::Name, Column::Name) | (Column::Kind, Column::Kind) | (Column::Fallback, Column::Fallback)
+        )
+    }
+}
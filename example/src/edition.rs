@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use symbols::EntityFilter;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "editions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub game: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub platform: String,
+    pub title: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl EntityFilter for Entity {}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Column::Game, Column::Game) | (Column::Platform, Column::Platform) | (Column::Title, Column::Title)
+        )
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use symbols::EntityFilter;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "events")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub name: String,
+    pub day: Date,
+    pub starts_at: DateTimeUtc,
+    pub ends_at: Option<DateTimeUtc>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl EntityFilter for Entity {}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Column::Name, Column::Name)
+                | (Column::Day, Column::Day)
+                | (Column::StartsAt, Column::StartsAt)
+                | (Column::EndsAt, Column::EndsAt)
+                | (Column::CreatedAt, Column::CreatedAt)
+        )
+    }
+}
@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use symbols::EntityFilter;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "status")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub code: String,
+    pub description: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl EntityFilter for Entity {}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Column::Id, Column::Id) | (Column::Code, Column::Code) | (Column::Description, Column::Description)
+        )
+    }
+}
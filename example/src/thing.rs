@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use symbols::EntityFilter;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "things")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub name: String,
+    pub checksum: Vec<u8>,
+    pub parent: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl EntityFilter for Entity {}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Column::Id, Column::Id)
+                | (Column::Name, Column::Name)
+                | (Column::Checksum, Column::Checksum)
+                | (Column::Parent, Column::Parent)
+        )
+    }
+}
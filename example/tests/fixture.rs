@@ -0,0 +1,319 @@
+#[example::example(
+    table = "best_selling_video_games",
+    fixture = "fixtures/mariadb/schema.sql",
+    rename(Pubg = "PUBG: Battlegrounds"),
+    platforms(split = " / "),
+    developer(generate = "Developer"),
+    publisher(generate = "Publisher", split = " / "),
+    lookup = "initial_release_date",
+    lookup_many("series", "developer"),
+    group("platforms", "publisher"),
+    derive_display,
+    derive_from_str,
+    derive_serde
+)]
+#[derive(Debug, PartialEq)]
+pub enum BestSellingVideoGame {}
+
+#[test]
+fn loads_from_fixture() {
+    assert_eq!(BestSellingVideoGame::Minecraft.as_str(), "Minecraft");
+    assert_eq!(BestSellingVideoGame::Minecraft.rank(), 1);
+    assert_eq!(BestSellingVideoGame::WiiFitPlus.initial_release_date(), "December 1, 2007");
+}
+
+#[test]
+fn renamed_variant_round_trips() {
+    assert_eq!(BestSellingVideoGame::Pubg.as_str(), "PUBG: Battlegrounds");
+    assert_eq!(BestSellingVideoGame::try_from("PUBG: Battlegrounds"), Ok(BestSellingVideoGame::Pubg));
+}
+
+#[test]
+fn splits_platforms() {
+    assert_eq!(BestSellingVideoGame::Minecraft.platforms(), &["Multi-platform"]);
+    assert_eq!(BestSellingVideoGame::TetrisNintendo.platforms(), &["Game Boy", "NES"]);
+}
+
+#[test]
+fn generates_companion_enums() {
+    assert_eq!(BestSellingVideoGame::Minecraft.developer(), Developer::MojangStudios);
+    assert_eq!(Developer::MojangStudios.as_str(), "Mojang Studios");
+    assert_eq!(Developer::try_from("Game Freak"), Ok(Developer::GameFreak));
+    assert_eq!(BestSellingVideoGame::PokemonRedGreenBlueYellow.publisher(), &[Publisher::Nintendo]);
+    assert_eq!(Publisher::try_from("The Pokemon Company"), Ok(Publisher::ThePokemonCompany));
+}
+
+#[test]
+fn derives_string_traits() {
+    assert_eq!(BestSellingVideoGame::Pubg.to_string(), "PUBG: Battlegrounds");
+    assert_eq!("Minecraft".parse(), Ok(BestSellingVideoGame::Minecraft));
+    assert_eq!(serde_json::to_string(&BestSellingVideoGame::Minecraft).unwrap(), "\"Minecraft\"");
+    assert_eq!(serde_json::from_str::<BestSellingVideoGame>("\"Minecraft\"").unwrap(), BestSellingVideoGame::Minecraft);
+    assert!(serde_json::from_str::<BestSellingVideoGame>("\"Mine\"").is_err());
+}
+
+#[test]
+fn lists_all_variants() {
+    assert_eq!(BestSellingVideoGame::COUNT, 50);
+    assert_eq!(BestSellingVideoGame::ALL.len(), BestSellingVideoGame::COUNT);
+    assert_eq!(BestSellingVideoGame::ALL[0], BestSellingVideoGame::Minecraft);
+    assert!(BestSellingVideoGame::iter().any(|game| *game == BestSellingVideoGame::Pubg));
+}
+
+#[test]
+fn looks_up_by_non_key_columns() {
+    const WII_FIT: Option<BestSellingVideoGame> = BestSellingVideoGame::get_by_initial_release_date("December 1, 2007");
+    assert_eq!(WII_FIT, Some(BestSellingVideoGame::WiiFitPlus));
+    assert_eq!(BestSellingVideoGame::get_by_initial_release_date("January 1, 1970"), None);
+    assert_eq!(BestSellingVideoGame::get_by_series("Minecraft"), &[BestSellingVideoGame::Minecraft]);
+    assert!(BestSellingVideoGame::get_by_series("Tetris").contains(&BestSellingVideoGame::TetrisNintendo));
+    assert!(BestSellingVideoGame::get_by_developer(Developer::GameFreak)
+        .contains(&BestSellingVideoGame::PokemonRedGreenBlueYellow));
+    assert_eq!(BestSellingVideoGame::get_by_series("Unknown"), &[]);
+}
+
+#[test]
+fn groups_by_non_key_columns() {
+    const GAME_BOY: &[BestSellingVideoGame] = BestSellingVideoGame::all_with_platforms("Game Boy");
+    assert!(GAME_BOY.contains(&BestSellingVideoGame::TetrisNintendo));
+    assert!(GAME_BOY.contains(&BestSellingVideoGame::PokemonRedGreenBlueYellow));
+    assert!(!GAME_BOY.contains(&BestSellingVideoGame::Minecraft));
+    assert!(BestSellingVideoGame::all_with_publisher(Publisher::ThePokemonCompany)
+        .iter()
+        .all(|game| game.publisher().contains(&Publisher::ThePokemonCompany)));
+    assert_eq!(BestSellingVideoGame::all_with_platforms("Atari 2600"), &[]);
+}
+
+#[example::example(
+    table = "best_selling_video_games",
+    fixture = "fixtures/mariadb/schema.sql",
+    variant = "series",
+    on_collision = "suffix"
+)]
+#[derive(Debug, PartialEq)]
+pub enum SeriesGame {}
+
+#[test]
+fn suffixes_colliding_variants() {
+    assert_eq!(SeriesGame::GrandTheftAuto.as_str(), "Grand Theft Auto V");
+    assert_eq!(SeriesGame::GrandTheftAuto2.as_str(), "Grand Theft Auto: San Andreas");
+    assert_eq!(SeriesGame::GrandTheftAuto3.as_str(), "Grand Theft Auto IV");
+    assert_eq!(SeriesGame::try_from("Mario Kart Wii"), Ok(SeriesGame::MarioKart2));
+}
+
+#[example::example(table = "status", fixture = "fixtures/status.csv", variant = "code")]
+#[derive(Debug, PartialEq)]
+pub enum Status {}
+
+#[example::example(table = "status", fixture = "fixtures/status.csv", variant = "code")]
+#[derive(Debug, PartialEq)]
+#[repr(u16)]
+pub enum WideStatus {}
+
+#[example::example(table = "status", fixture = "fixtures/status.csv", variant = "code")]
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+pub enum CStatus {}
+
+#[test]
+fn integer_keys_become_discriminants() {
+    assert_eq!(Status::Archived as u8, 200);
+    assert_eq!(Status::Suspended.id(), 2);
+    assert_eq!(Status::Active.description(), "Currently running");
+    assert_eq!(Status::try_from(200), Ok(Status::Archived));
+    assert!(Status::try_from(3).is_err());
+    assert_eq!(std::mem::size_of::<Status>(), 1);
+}
+
+#[test]
+fn integer_keys_keep_declared_repr() {
+    let id: u16 = WideStatus::Archived.id();
+    assert_eq!(id, 200);
+    assert_eq!(std::mem::size_of::<WideStatus>(), 2);
+    let id: u8 = CStatus::Archived.id();
+    assert_eq!(id, 200);
+    assert_eq!(CStatus::try_from(1), Ok(CStatus::Active));
+}
+
+#[example::example(table = "drinks", fixture = "fixtures/drinks.json")]
+#[derive(Debug, PartialEq)]
+pub enum Drink {}
+
+// proc-macro crates can't export their entities, so the ActiveEnum is compiled here too
+#[path = "../src/drink.rs"]
+#[allow(dead_code)]
+mod drink;
+
+use drink::Tea;
+
+#[example::example(
+    table = "drinks",
+    fixture = "fixtures/drinks.json",
+    rename(Morning = "Morning, \"strong\""),
+    kind(active_enum = "drink::Tea"),
+    fallback(active_enum = "drink::Tea"),
+    group = "kind"
+)]
+#[derive(Debug, PartialEq)]
+pub enum TypedDrink {}
+
+#[test]
+fn active_enums_are_strings_by_default() {
+    assert_eq!(Drink::MorningStrong.kind(), "B");
+    assert_eq!(Drink::Afternoon.fallback(), Some("B"));
+}
+
+#[test]
+fn active_enums_map_to_their_rust_enum() {
+    assert_eq!(TypedDrink::Morning.kind(), Tea::BreakfastTea);
+    assert_eq!(TypedDrink::Afternoon.kind(), Tea::EverydayTea);
+    assert_eq!(TypedDrink::Morning.fallback(), None);
+    assert_eq!(TypedDrink::Afternoon.fallback(), Some(Tea::BreakfastTea));
+    assert_eq!(TypedDrink::all_with_kind(Tea::EverydayTea), &[TypedDrink::Afternoon]);
+}
+
+#[example::example(table = "editions", fixture = "fixtures/editions.csv")]
+#[derive(Debug, PartialEq)]
+pub enum Edition {}
+
+#[example::example(table = "editions", fixture = "fixtures/editions.csv", platform(generate = "Platform"))]
+#[derive(Debug, PartialEq)]
+pub enum TypedEdition {}
+
+#[test]
+fn composite_keys_round_trip() {
+    const KEYS: (&str, &str) = Edition::Tetris_Nes.keys();
+    assert_eq!(KEYS, ("tetris", "nes"));
+    assert_eq!(Edition::Zelda_Nes.game(), "zelda");
+    assert_eq!(Edition::Tetris_GameBoy.platform(), "game_boy");
+    assert_eq!(Edition::Tetris_Nes.title(), "Tetris (NES)");
+    assert_eq!(Edition::get_by_game("tetris"), &[Edition::Tetris_GameBoy, Edition::Tetris_Nes]);
+    assert_eq!(Edition::try_from(KEYS), Ok(Edition::Tetris_Nes));
+    assert!(Edition::ALL.iter().all(|edition| Edition::try_from(edition.keys()).as_ref() == Ok(edition)));
+    assert_eq!(Edition::try_from(("zelda", "game_boy")), Err(String::from("Unknown Edition (zelda, game_boy)")));
+}
+
+#[test]
+fn composite_keys_keep_replacements() {
+    assert_eq!(TypedEdition::Zelda_Nes.keys(), ("zelda", Platform::Nes));
+    assert_eq!(TypedEdition::Tetris_GameBoy.platform(), Platform::GameBoy);
+    assert_eq!(TypedEdition::try_from(TypedEdition::Tetris_Nes.keys()), Ok(TypedEdition::Tetris_Nes));
+    assert_eq!(
+        TypedEdition::try_from(("zelda", Platform::GameBoy)),
+        Err(String::from("Unknown TypedEdition (zelda, GameBoy)"))
+    );
+}
+
+#[example::example(table = "events", fixture = "fixtures/events.json", created_at(skip))]
+#[derive(Debug, PartialEq)]
+pub enum Event {}
+
+#[test]
+fn chrono_fields_round_trip() {
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    const DAY: NaiveDate = Event::Launch.day();
+    assert_eq!(DAY, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    assert_eq!(Event::Launch.starts_at(), "2024-02-29T18:30:15Z".parse::<DateTime<Utc>>().unwrap());
+    assert_eq!(Event::Keynote.starts_at(), "1999-12-31T23:59:59.250Z".parse::<DateTime<Utc>>().unwrap());
+    assert_eq!(Event::Launch.ends_at(), None);
+    assert_eq!(Event::Keynote.ends_at(), Some("2000-01-01T01:00:00Z".parse::<DateTime<Utc>>().unwrap()));
+}
+
+#[example::example(table = "things", fixture = "fixtures/things.json", variant = "name")]
+#[derive(Debug, PartialEq)]
+pub enum Thing {}
+
+#[test]
+fn uuid_keys_and_binary_fields() {
+    let root = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    assert_eq!(Thing::Root.id(), root);
+    assert_eq!(Thing::Leaf.id(), uuid::Uuid::parse_str("a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8").unwrap());
+    assert_eq!(Thing::try_from(root), Ok(Thing::Root));
+    assert!(Thing::try_from(uuid::Uuid::nil()).is_err());
+    assert_eq!(Thing::Root.checksum(), &[0, 255, 16, 34]);
+    assert!(Thing::Leaf.checksum().is_empty());
+    assert_eq!(Thing::Root.parent(), None);
+    assert_eq!(Thing::Leaf.parent(), Some(root));
+}
+
+mod catalog {
+    #[derive(Debug, PartialEq)]
+    pub enum Platform {
+        GameBoy,
+        Nes,
+    }
+}
+
+#[example::example(table = "editions", fixture = "fixtures/editions.csv", platform(type = "crate::catalog::Platform"))]
+#[derive(Debug, PartialEq)]
+pub enum CatalogEdition {}
+
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq)]
+pub struct Label(&'static str);
+
+impl Label {
+    const fn new(code: &'static str) -> Self {
+        Label(code)
+    }
+}
+
+#[example::example(
+    table = "status",
+    fixture = "fixtures/status.csv",
+    variant = "code",
+    code(type = "Label", fn = "new"),
+    description(type = "Cow<'static, str>", fn = "Cow::Borrowed")
+)]
+#[derive(Debug, PartialEq)]
+pub enum LabeledStatus {}
+
+#[test]
+fn replacements_resolve_paths() {
+    assert_eq!(CatalogEdition::Tetris_GameBoy.platform(), catalog::Platform::GameBoy);
+    assert_eq!(CatalogEdition::Zelda_Nes.keys(), ("zelda", catalog::Platform::Nes));
+    assert_eq!(LabeledStatus::Suspended.code(), Label("suspended"));
+    assert_eq!(LabeledStatus::Active.description(), Cow::Borrowed("Currently running"));
+    assert!(matches!(LabeledStatus::Archived.description(), Cow::Borrowed("Kept for history")));
+}
+
+#[example::example(
+    table = "best_selling_video_games",
+    fixture = "fixtures/mariadb/schema.sql",
+    rename(Pubg = "PUBG: Battlegrounds"),
+    developer(generate = "Company"),
+    publisher(generate = "Company", split = " / ")
+)]
+#[derive(Debug, PartialEq)]
+pub enum CompanyGame {}
+
+#[test]
+fn shared_generated_enums_merge_values() {
+    assert_eq!(CompanyGame::Minecraft.developer(), Company::MojangStudios);
+    assert_eq!(CompanyGame::Minecraft.publisher(), &[Company::XboxGameStudios]);
+    assert_eq!(CompanyGame::Terraria.developer(), Company::ReLogic);
+    assert_eq!(CompanyGame::Terraria.publisher(), &[Company::ReLogic, Company::_505Games]);
+    assert_eq!(Company::try_from("Game Freak"), Ok(Company::GameFreak));
+    assert_eq!(Company::try_from("Nintendo"), Ok(Company::Nintendo));
+}
+
+#[example::example(table = "editions", fixture = "fixtures/editions.csv", platform(type = "Label", fn = "new"))]
+#[derive(Debug, PartialEq)]
+pub enum LabeledEdition {}
+
+#[test]
+fn composite_keys_keep_raw_fn_values() {
+    const KEYS: (&str, &str) = LabeledEdition::Tetris_Nes.keys();
+    assert_eq!(KEYS, ("tetris", "nes"));
+    assert_eq!(LabeledEdition::Tetris_GameBoy.platform(), Label("game_boy"));
+    assert_eq!(LabeledEdition::Tetris_GameBoy.platform_raw(), "game_boy");
+    assert_eq!(LabeledEdition::get_by_platform("nes"), &[LabeledEdition::Tetris_Nes, LabeledEdition::Zelda_Nes]);
+    assert_eq!(LabeledEdition::try_from(KEYS), Ok(LabeledEdition::Tetris_Nes));
+    assert!(LabeledEdition::ALL.iter().all(|edition| LabeledEdition::try_from(edition.keys()).as_ref() == Ok(edition)));
+    assert_eq!(
+        LabeledEdition::try_from(("zelda", "game_boy")),
+        Err(String::from("Unknown LabeledEdition (zelda, game_boy)"))
+    );
+}
@@ -1,14 +1,12 @@
 use std::fmt;
 
-use sea_orm::{EnumIter, Iterable};
-
 #[example::example(
     table = "best_selling_video_games",
     platforms(type = "Platforms", fn = "from_str"),
     developer = "Developer",
     publisher(type = "Publisher", fn = "from_str")
 )]
-#[derive(Debug, EnumIter)]
+#[derive(Debug)]
 pub enum BestSellingVideoGame {}
 
 #[derive(Debug)]
@@ -3,7 +3,7 @@
 
 //! # Symbols-models
 //!
-//! Shared traits from Symbols proc-macro-utility, to be able to share models between macros and real applications.
+//! Shared traits and types from Symbols proc-macro-utility, to be able to share models between macros and real applications.
 
 use sea_orm::{
     sea_query::{Expr, SimpleExpr},
@@ -20,3 +20,25 @@ pub trait EntityFilter: EntityTrait + Default {
         Expr::val(1).eq(1)
     }
 }
+
+/// Const-constructible JSON value, JSON fields are represented as a tree of these values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Json {
+    /// JSON null
+    Null,
+    /// JSON boolean
+    Bool(bool),
+    /// JSON number fitting a signed integer
+    Int(i64),
+    /// JSON number fitting only an unsigned integer
+    UInt(u64),
+    /// JSON number with a fractional part
+    Float(f64),
+    /// JSON string
+    String(&'static str),
+    /// JSON array
+    Array(&'static [Json]),
+    /// JSON object, as a list of key-value pairs in the order `serde_json` yields them:
+    /// sorted by key, unless `serde_json/preserve_order` feature is enabled
+    Object(&'static [(&'static str, Json)]),
+}
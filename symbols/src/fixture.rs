@@ -0,0 +1,453 @@
+//! Offline data loading from fixture files
+
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    iter::Peekable,
+    path::{Path, PathBuf},
+    str::Chars,
+};
+
+use heck::ToSnakeCase;
+
+use proc_macro2::Span;
+
+use sea_orm::{EntityName, EntityTrait, IdenStatic, Iterable};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use serde_json::{Map, Number, Value as JsonValue};
+
+use symbols_models::EntityFilter;
+
+use syn::NestedMeta;
+
+use crate::{get_str_arg, DataSource};
+
+/// Fixture file containing rows for an entity
+///
+/// The file format is picked by extension:
+/// * `json`: an array of objects, deserialized as they are into models
+/// * `csv`: a file with headers, where every header is either a database column name or a model field name
+/// * `sql`: a SQL script, every `INSERT INTO` statement targeting the entity table is parsed and its rows are loaded;
+///   string literals follow MySQL rules, where backslash starts an escape sequence, so scripts relying on Postgres
+///   `standard_conforming_strings` will have their backslashes changed
+///
+/// Relative paths are resolved against the directory of the crate invoking the macro.
+pub struct Fixture {
+    path: PathBuf,
+}
+
+impl Fixture {
+    /// Creates a new fixture pointing to given file
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        if path.is_relative() {
+            if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
+                return Fixture { path: Path::new(&dir).join(path) };
+            }
+        }
+        Fixture { path: path.to_path_buf() }
+    }
+
+    /// Path of the fixture file, relative paths are already resolved
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads all rows for given entity
+    pub fn load<M>(&self) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+    where
+        M: EntityTrait + Default,
+        <M as EntityTrait>::Model: DeserializeOwned,
+    {
+        match self.path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => {
+                let file = fs::File::open(&self.path).map_err(|e| self.error(e))?;
+                serde_json::from_reader(io::BufReader::new(file)).map_err(|e| self.error(e))
+            }
+            Some("csv") => {
+                let mut reader = csv::Reader::from_path(&self.path).map_err(|e| self.error(e))?;
+                let fields = get_fields::<M>();
+                let headers = reader
+                    .headers()
+                    .map_err(|e| self.error(e))?
+                    .iter()
+                    .map(|header| fields.get(header).map(String::as_str).unwrap_or(header))
+                    .collect();
+                reader.set_headers(headers);
+                reader.deserialize().collect::<Result<_, _>>().map_err(|e| self.error(e))
+            }
+            Some("sql") => {
+                let sql = fs::read_to_string(&self.path).map_err(|e| self.error(e))?;
+                let instance = M::default();
+                let fields = get_fields::<M>();
+                let columns = <M as EntityTrait>::Column::iter().map(|col| col.as_str().to_owned()).collect::<Vec<_>>();
+                parse_inserts(&sql, EntityName::table_name(&instance))
+                    .map_err(|e| self.error(e))?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (names, values))| {
+                        let names = names.as_ref().unwrap_or(&columns);
+                        if names.len() != values.len() {
+                            return Err(self.error(format!(
+                                "row {} has {} values, expected {}",
+                                index + 1,
+                                values.len(),
+                                names.len()
+                            )));
+                        }
+                        let row = names
+                            .iter()
+                            .map(|name| fields.get(name).cloned().unwrap_or_else(|| name.clone()))
+                            .zip(values)
+                            .collect::<Map<_, _>>();
+                        serde_json::from_value(JsonValue::Object(row))
+                            .map_err(|e| self.error(format!("row {}: {}", index + 1, e)))
+                    })
+                    .collect()
+            }
+            _ => Err(self.error("unsupported file extension, expected one of json, csv or sql")),
+        }
+    }
+
+    fn error(&self, e: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new(Span::call_site(), format!("Error loading fixture {}: {}", self.path.display(), e))
+    }
+}
+
+impl DataSource for Fixture {
+    async fn fetch<M>(&self) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+    where
+        M: EntityTrait + EntityFilter + Default,
+        <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static,
+    {
+        self.load::<M>()
+    }
+}
+
+/// Searches for a fixture file between macro arguments, in the form #[macro(fixture = "path/to/file.sql")]
+pub(crate) fn get_fixture(args: &[NestedMeta]) -> Option<Fixture> {
+    get_str_arg(args, "fixture").map(|s| Fixture::new(s.value()))
+}
+
+/// Maps database column names to model field names
+fn get_fields<M: EntityTrait>() -> HashMap<String, String> {
+    <M as EntityTrait>::Column::iter()
+        .map(|col| (col.as_str().to_owned(), format!("{col:?}").to_snake_case()))
+        .collect()
+}
+
+/// Column names, when specified, and values of every row inserted into a table
+type Rows = Vec<(Option<Vec<String>>, Vec<JsonValue>)>;
+
+/// Extracts rows from every `INSERT INTO` statement targeting given table
+fn parse_inserts(sql: &str, table: &str) -> Result<Rows, String> {
+    let mut tokens = tokenize(sql)?.into_iter().peekable();
+    let mut rows = vec![];
+
+    while let Some(token) = tokens.next() {
+        if token == Token::Punct(';') {
+            continue;
+        }
+        if !token.is_keyword("INSERT") {
+            // skip any other statement
+            while tokens.next_if(|t| *t != Token::Punct(';')).is_some() {}
+            tokens.next();
+            continue;
+        }
+
+        while tokens.next_if(|t| t.is_keyword("IGNORE")).is_some() {}
+        expect(&mut tokens, |t| t.is_keyword("INTO"), "INTO")?;
+        let mut name = identifier(&mut tokens)?;
+        while tokens.next_if_eq(&Token::Punct('.')).is_some() {
+            name = identifier(&mut tokens)?;
+        }
+
+        let columns = if tokens.next_if_eq(&Token::Punct('(')).is_some() {
+            let mut columns = vec![identifier(&mut tokens)?];
+            while tokens.next_if_eq(&Token::Punct(',')).is_some() {
+                columns.push(identifier(&mut tokens)?);
+            }
+            expect(&mut tokens, |t| *t == Token::Punct(')'), ")")?;
+            Some(columns)
+        } else {
+            None
+        };
+
+        expect(&mut tokens, |t| t.is_keyword("VALUES") || t.is_keyword("VALUE"), "VALUES")?;
+        loop {
+            expect(&mut tokens, |t| *t == Token::Punct('('), "(")?;
+            let mut values = vec![value(&mut tokens)?];
+            while tokens.next_if_eq(&Token::Punct(',')).is_some() {
+                values.push(value(&mut tokens)?);
+            }
+            expect(&mut tokens, |t| *t == Token::Punct(')'), ")")?;
+            if name == table {
+                rows.push((columns.clone(), values));
+            }
+            if tokens.next_if_eq(&Token::Punct(',')).is_none() {
+                break;
+            }
+        }
+        // skip trailing clauses, like ON DUPLICATE KEY UPDATE
+        while tokens.next_if(|t| *t != Token::Punct(';')).is_some() {}
+    }
+
+    Ok(rows)
+}
+
+/// SQL tokens
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    String(String),
+    Number(String),
+    Punct(char),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn expect<I>(tokens: &mut Peekable<I>, f: impl Fn(&Token) -> bool, expected: &str) -> Result<(), String>
+where
+    I: Iterator<Item = Token>,
+{
+    match tokens.next() {
+        Some(t) if f(&t) => Ok(()),
+        Some(t) => Err(format!("expected {expected}, found {t:?}")),
+        None => Err(format!("expected {expected}, found end of file")),
+    }
+}
+
+fn identifier<I>(tokens: &mut Peekable<I>) -> Result<String, String>
+where
+    I: Iterator<Item = Token>,
+{
+    match tokens.next() {
+        Some(Token::Word(w) | Token::Quoted(w)) => Ok(w),
+        Some(t) => Err(format!("expected identifier, found {t:?}")),
+        None => Err(String::from("expected identifier, found end of file")),
+    }
+}
+
+fn value<I>(tokens: &mut Peekable<I>) -> Result<JsonValue, String>
+where
+    I: Iterator<Item = Token>,
+{
+    match tokens.next() {
+        Some(Token::String(s)) => Ok(JsonValue::String(s)),
+        Some(Token::Number(n)) => number(&n),
+        Some(Token::Punct(sign @ ('-' | '+'))) => match tokens.next() {
+            Some(Token::Number(n)) => number(&format!("{sign}{n}")),
+            t => Err(format!("expected number, found {t:?}")),
+        },
+        Some(t) if t.is_keyword("NULL") => Ok(JsonValue::Null),
+        Some(t) if t.is_keyword("TRUE") => Ok(JsonValue::Bool(true)),
+        Some(t) if t.is_keyword("FALSE") => Ok(JsonValue::Bool(false)),
+        Some(t) => Err(format!("unsupported value {t:?}")),
+        None => Err(String::from("expected value, found end of file")),
+    }
+}
+
+fn number(n: &str) -> Result<JsonValue, String> {
+    let n = n.strip_prefix('+').unwrap_or(n);
+    if let Ok(i) = n.parse::<i64>() {
+        Ok(JsonValue::from(i))
+    } else if let Ok(u) = n.parse::<u64>() {
+        Ok(JsonValue::from(u))
+    } else {
+        n.parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(JsonValue::Number)
+            .ok_or_else(|| format!("invalid number {n}"))
+    }
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, String> {
+    let mut chars = sql.chars().peekable();
+    let mut tokens = vec![];
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '-' if chars.peek() == Some(&'-') => skip_line(&mut chars),
+            '#' => skip_line(&mut chars),
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == '*' => break,
+                        Some(c) => prev = c,
+                        None => return Err(String::from("unterminated comment")),
+                    }
+                }
+            }
+            '\'' => tokens.push(Token::String(quoted(&mut chars, '\'', true)?)),
+            '"' | '`' => tokens.push(Token::Quoted(quoted(&mut chars, c, false)?)),
+            c if c.is_ascii_digit() || (c == '.' && chars.peek().is_some_and(char::is_ascii_digit)) => {
+                let mut n = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.') {
+                    n.push(c);
+                    // exponent sign
+                    if c == 'e' || c == 'E' {
+                        if let Some(sign) = chars.next_if(|c| *c == '-' || *c == '+') {
+                            n.push(sign);
+                        }
+                    }
+                }
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut w = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '$') {
+                    w.push(c);
+                }
+                tokens.push(Token::Word(w));
+            }
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn skip_line(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| *c != '\n').is_some() {}
+}
+
+/// Reads a quoted string, a doubled quote char is an escaped quote char
+fn quoted(chars: &mut Peekable<Chars>, quote: char, backslash: bool) -> Result<String, String> {
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            Some(c) if c == quote => {
+                if chars.next_if_eq(&quote).is_some() {
+                    s.push(quote);
+                } else {
+                    return Ok(s);
+                }
+            }
+            Some('\\') if backslash => match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('0') => s.push('\0'),
+                Some('Z') => s.push('\x1a'),
+                Some(c) => s.push(c),
+                None => return Err(String::from("unterminated string")),
+            },
+            Some(c) => s.push(c),
+            None => return Err(String::from("unterminated string")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use serde_json::json;
+
+    use super::{parse_inserts, tokenize, Fixture, Token};
+
+    use crate::test_model::{Entity, Model};
+
+    #[test]
+    fn tokenizes_quoted_strings() {
+        let tokens = tokenize(r#"'it''s' 'a\nb\'c\\' "quoted""id" `back``tick`"#).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::String(String::from("it's")),
+                Token::String(String::from("a\nb'c\\")),
+                Token::Quoted(String::from("quoted\"id")),
+                Token::Quoted(String::from("back`tick")),
+            ]
+        );
+        assert!(tokenize("'unterminated").is_err());
+    }
+
+    #[test]
+    fn skips_comments() {
+        let sql = "/* block\n comment */ -- line comment\n# hash comment\nINSERT INTO games VALUES ('a', 1);";
+        assert_eq!(parse_inserts(sql, "games").unwrap(), vec![(None, vec![json!("a"), json!(1)])]);
+        assert!(tokenize("/* unterminated").is_err());
+    }
+
+    #[test]
+    fn parses_schema_qualified_tables() {
+        let sql = "INSERT INTO `db`.`games` VALUES ('a', 1); INSERT INTO other.games VALUES ('b', 2);";
+        let rows = parse_inserts(sql, "games").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(parse_inserts("INSERT INTO db.other VALUES ('a', 1);", "games").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_multi_row_values() {
+        let sql =
+            "CREATE TABLE games (name text); INSERT IGNORE INTO games (name, `rank`) VALUES ('a', 1), ('b', NULL);";
+        let columns = Some(vec![String::from("name"), String::from("rank")]);
+        assert_eq!(
+            parse_inserts(sql, "games").unwrap(),
+            vec![(columns.clone(), vec![json!("a"), json!(1)]), (columns, vec![json!("b"), json!(null)])]
+        );
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let sql = "INSERT INTO games VALUES (-1, +2, 1.5e3, -2E-2, .5, 18446744073709551615, TRUE, false);";
+        let (_, values) = parse_inserts(sql, "games").unwrap().remove(0);
+        assert_eq!(
+            values,
+            vec![
+                json!(-1),
+                json!(2),
+                json!(1500.0),
+                json!(-0.02),
+                json!(0.5),
+                json!(u64::MAX),
+                json!(true),
+                json!(false)
+            ]
+        );
+        assert!(parse_inserts("INSERT INTO games VALUES (1x);", "games").is_err());
+    }
+
+    #[test]
+    fn skips_on_duplicate_key_update() {
+        let sql = "INSERT INTO games VALUES ('a', 1) ON DUPLICATE KEY UPDATE `rank` = VALUES(`rank`);\n\
+            INSERT INTO games VALUES ('b', 2);";
+        let rows = parse_inserts(sql, "games").unwrap();
+        assert_eq!(rows, vec![(None, vec![json!("a"), json!(1)]), (None, vec![json!("b"), json!(2)])]);
+    }
+
+    #[test]
+    fn loads_sql_rows() {
+        let path = env::temp_dir().join("symbols_fixture_loads_sql_rows.sql");
+        fs::write(&path, "INSERT INTO games (name, `rank`) VALUES ('a', 1); INSERT INTO games VALUES ('b', 2, 'c');")
+            .unwrap();
+        let rows = Fixture::new(&path).load::<Entity>().unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                Model { name: String::from("a"), rank: 1, series: None },
+                Model { name: String::from("b"), rank: 2, series: Some(String::from("c")) }
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_column_count() {
+        let path = env::temp_dir().join("symbols_fixture_rejects_wrong_column_count.sql");
+        fs::write(&path, "INSERT INTO games VALUES ('a', 1, NULL), ('b', 2);").unwrap();
+        let e = Fixture::new(&path).load::<Entity>().unwrap_err();
+        assert!(e.to_string().ends_with("row 2 has 2 values, expected 3"), "{e}");
+    }
+}
@@ -0,0 +1,77 @@
+//! Identifiers sanitization
+
+use deunicode::deunicode;
+
+use heck::ToUpperCamelCase;
+
+use proc_macro2::{Ident, Span};
+
+/// Keywords that can't be used as raw identifiers
+const NON_RAW_KEYWORDS: [&str; 4] = ["crate", "self", "super", "Self"];
+
+/// Builds a variant identifier out of given values
+/// * unicode chars are transliterated to ASCII
+/// * every value is converted to UpperCamelCase, then values are joined with `_`
+/// * a leading digit gets prefixed with given prefix
+/// * keywords become raw identifiers, or get a trailing `_` when they can't be raw
+pub(crate) fn to_variant<S: AsRef<str>>(values: &[S], digit_prefix: &str) -> Result<Ident, String> {
+    let mut name = values.iter().map(|v| deunicode(v.as_ref()).to_upper_camel_case()).collect::<Vec<_>>().join("_");
+    if name.trim_matches('_').is_empty() {
+        let values = values.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        return Err(format!("\"{}\" doesn't contain any alphanumeric char", values.join(", ")));
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, digit_prefix);
+    }
+
+    if syn::parse_str::<Ident>(&name).is_ok() {
+        Ok(Ident::new(&name, Span::call_site()))
+    } else if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+        Ok(Ident::new(&name, Span::call_site()))
+    } else if syn::parse_str::<Ident>(&format!("r#{name}")).is_ok() {
+        Ok(Ident::new_raw(&name, Span::call_site()))
+    } else {
+        Err(format!("\"{name}\" is not a valid identifier"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_variant;
+
+    fn variant(values: &[&str], digit_prefix: &str) -> String {
+        to_variant(values, digit_prefix).unwrap().to_string()
+    }
+
+    #[test]
+    fn camel_cases_and_joins_values() {
+        assert_eq!(variant(&["Wii Fit / Plus"], "_"), "WiiFitPlus");
+        assert_eq!(variant(&["Tetris", "11"], "_"), "Tetris_11");
+    }
+
+    #[test]
+    fn prefixes_leading_digits() {
+        assert_eq!(variant(&["2K Games"], "_"), "_2kGames");
+        assert_eq!(variant(&["2K Games"], "N"), "N2kGames");
+        assert_eq!(variant(&["007"], "Agent"), "Agent007");
+    }
+
+    #[test]
+    fn escapes_keywords() {
+        assert_eq!(variant(&["self"], "_"), "Self_");
+        assert_eq!(variant(&["Self"], "_"), "Self_");
+    }
+
+    #[test]
+    fn transliterates_unicode() {
+        assert_eq!(variant(&["Ünïcode"], "_"), "Unicode");
+        assert_eq!(variant(&["Pokémon Rojo"], "_"), "PokemonRojo");
+    }
+
+    #[test]
+    fn rejects_values_without_alphanumeric_chars() {
+        assert_eq!(to_variant(&[""], "_").unwrap_err(), "\"\" doesn't contain any alphanumeric char");
+        assert_eq!(to_variant(&["-", "/"], "_").unwrap_err(), "\"-, /\" doesn't contain any alphanumeric char");
+    }
+}
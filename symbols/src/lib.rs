@@ -4,103 +4,369 @@
 //! # Symbols
 //!
 //! This is an utility to build a proc-macro that connects to a database, retrieves data from given table and populates an enum variants with primary keys values  
-//! It also generates a method for every non-primary-key field, and, when there are multiple primary keys, a costructor for every possible subset of primary keys
+//! It also generates a method for every non-primary-key field, and, when there are multiple primary keys, a costructor for every possible subset of primary keys  
+//! The [declare_symbols_macro] macro can be used to generate the proc-macro entry point from a list of entities
 
-use std::{collections::HashMap, env, fs, future::Future, io};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Debug,
+};
 
-use heck::{ToSnakeCase, ToUpperCamelCase};
+use heck::ToSnakeCase;
 
 use itertools::Itertools;
 
-use proc_macro2::{Ident, Literal, Span, TokenStream};
+use proc_macro2::{Delimiter, Ident, Literal, Span, TokenStream, TokenTree};
 
 use quote::quote;
 
 use sea_orm::{
-    DatabaseConnection, EntityName, EntityTrait, Iterable, ModelTrait, PrimaryKeyToColumn, QueryFilter, Value,
+    prelude::Uuid, sea_query::ColumnType, ColumnTrait, EntityTrait, Iterable, ModelTrait, PrimaryKeyToColumn, Value,
 };
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use serde_json::Value as JsonValue;
+
 pub use symbols_models::EntityFilter;
 
-use syn::{punctuated::Punctuated, token::Comma, Fields, ItemEnum, Lit, LitBool, Meta, NestedMeta, Variant};
+mod fixture;
+pub use fixture::Fixture;
+
+mod ident;
+
+mod macros;
+pub use macros::{get_conn, get_table};
+
+mod source;
+pub use source::{Cache, DataSource, Memory};
+
+#[cfg(test)]
+mod test_model;
+
+use syn::{
+    parse_quote, punctuated::Punctuated, spanned::Spanned, token::Comma, Fields, ItemEnum, Lit, LitBool, LitInt,
+    LitStr, Meta, NestedMeta, Path, Token, Type, Variant, Visibility,
+};
 
-use tracing::{error, info};
+use tracing::info;
+
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, Timelike};
+
+#[cfg(feature = "postgres-array")]
+use sea_orm::sea_query::ArrayType;
 
 /// Main function  
-/// Given a database model (via generics), an enum item, a list of arguments and an async function to retrieve a database connection
+/// Given a database model (via generics), an enum item, a list of arguments and a data source
 /// it populates the enum using primary key(s) values.  
-/// Only string-typed primary keys are supported.
+/// When a `fixture` argument is present, data is loaded from that file instead of the given source.  
+/// String-typed and integer-typed primary keys are supported, the latter only as single primary key.
 ///
 /// When a single primary key is present, it simply generate an as_str method and a TryFrom<&str> implementation.  
-/// When multiple primary keys are present, it generates a costructor for every possible subset of primary keys.
+/// On top of them, Display, FromStr and serde implementations can be generated with #[macro(derive_display, derive_from_str, derive_serde)].  
+/// When the single primary key is an integer, it becomes the variant discriminant, with a `#[repr(..)]` picked from values range,
+/// and it generates an id method and a TryFrom<i64> implementation instead.
+/// With `uuid` feature, the single primary key can be an UUID, that generates an id method and a TryFrom<Uuid> implementation.  
+/// Variant names are then taken from the column given with #[macro(variant = "column")].  
+/// The variant argument can be used with string-typed primary keys too, even with a list of columns in the form #[macro(variant("column1", "column2"))],
+/// while primary keys remain the lookup key for as_str, TryFrom and constructors.
+///
+/// Variant names are sanitized: unicode chars are transliterated, names starting with a digit get prefixed
+/// (by `_`, or by the value of #[macro(digit_prefix = "prefix")]) and keywords become raw identifiers or get a trailing `_`.  
+/// Variant name collisions are errors, unless #[macro(on_collision = "suffix")] is given, that adds a numeric suffix to later rows.  
+/// Variant names can also be picked by hand, in the form #[macro(rename(Variant = "key"))], where `key` is the primary key value.
+/// When multiple primary keys are present, it generates a costructor for every possible subset of primary keys,
+/// a method for every primary key, a keys method returning them all and a TryFrom<(&str, ..)> implementation,
+/// where primary keys replaced by enums must implement Debug, and primary keys replaced by fn calls are kept raw, with a `<key>_raw` method.
+///
+/// Non-key columns can generate reverse lookups in the form #[macro(lookup = "column")] or #[macro(lookup("column1", "column2"))],
+/// a get_by_column constructor returning an `Option<Self>`, failing when column values aren't unique,
+/// while #[macro(lookup_many = "column")] generates a get_by_column constructor returning `&'static [Self]`.  
+/// Rows can be grouped by non-key columns in the form #[macro(group = "column")] or #[macro(group("column1", "column2"))],
+/// generating an all_with_column method returning `&'static [Self]`, where split and array fields group rows under each of their pieces.  
+/// Lookups and groups take the replacement enum as parameter, when the column has a basic replacement.
+///
+/// Every variant is listed in an ALL constant, together with a COUNT constant and an iter method.
 ///
 /// For every non-primary key field of a supported type, it generates a const method to retrieve it.
 ///
-/// Replacements can be done on every string-typed field, even primary keys, and are done using annotated parameters.  
+/// With `chrono` feature, date, time, datetime and UTC datetime fields generate const methods too.  
+/// With `rust_decimal` feature, decimal and big decimal fields generate const methods returning `rust_decimal::Decimal`.  
+/// Binary fields generate const methods returning `&'static [u8]`, and, with `uuid` feature, UUID fields generate const methods returning `uuid::Uuid` (the crate using the macro needs to depend on `uuid`).  
+/// JSON fields generate const methods returning a `symbols_models::Json` tree,
+/// or can be mapped to a custom type in the form #[macro(field(type = "bar", fn = "foo"))], where `foo` is a const fn accepting a `symbols_models::Json`.  
+/// With `postgres-array` feature, array fields generate const methods returning `&'static [T]`, where `T` is mapped like scalar fields, replacements included.  
+/// Any field can be opted out, in the form #[macro(field(skip))].
+///
+/// Replacements can be done on every field, even string-typed primary keys, and are done using annotated parameters.  
 /// Two type of replacements are supported:
 /// * basic: written in the form #[macro(field = "enum")] or #[macro(field(type = "enum"))], where we are telling to replace string values from `field` with variants from enum `enum`, variant names will be the CamelCase version of field value.
 /// * advanced: written in the form #[macro(field(type = "bar", fn = "foo"))], where we are telling to replace string values from `field` with a call to method `foo` from struct/enum `bar`, method output is expected to be of type `bar`.
-pub async fn symbols<M, F, Fut>(item: &mut ItemEnum, args: &[NestedMeta], get_conn: F) -> syn::Result<TokenStream>
+///   This is the only replacement available on non-string fields, where the value of any supported type is passed as parameter.
+///
+/// Replacement types are parsed as Rust types and functions as paths, a single-segment function is searched on the replacement type.
+/// String fields can be split in the form #[macro(field(type = "enum", split = " / "))], generating a method returning `&'static [enum]`,
+/// where every piece is replaced on its own, or kept as `&'static str` when no type is given.
+/// Replacement enums can be generated from distinct string values in the form #[macro(field(generate = "enum"))], even when splitting,
+/// the generated enum has an as_str method and a TryFrom<&str> implementation, fields generating the same enum share it.
+///
+/// Arguments naming neither a known argument nor a column are errors, suggesting the most similar name.
+///
+/// ActiveEnum fields can be mapped to their Rust enum in the form #[macro(field(active_enum = "enum"))], where variant names are read from the serialized model,
+/// only fields stored as database enums are accepted, otherwise ActiveEnum fields are treated as their database value.
+pub async fn symbols<M, S>(item: &mut ItemEnum, args: &[NestedMeta], source: S) -> syn::Result<TokenStream>
 where
     M: EntityTrait + EntityFilter + Default,
-    <M as EntityTrait>::Model: Serialize + DeserializeOwned,
+    <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static,
     <M as EntityTrait>::Column: PartialEq,
-    F: Fn() -> Fut,
-    Fut: Future<Output = syn::Result<DatabaseConnection>>,
+    S: DataSource,
 {
+    validate_args::<M>(args)?;
+
     let name = &item.ident;
     let primary_keys = <M as EntityTrait>::PrimaryKey::iter().map(|k| k.into_column()).collect::<Vec<_>>();
 
     let mut constructors = HashMap::new();
     let mut methods = HashMap::new();
 
-    let data = get_data::<M, _, _>(get_conn).await?;
+    // data errors point to the table argument, or to the fixture argument when loading from a fixture file
+    let table_span = get_str_arg(args, "table").map_or_else(Span::call_site, LitStr::span);
+    // fixture files are included in the output too, so that cargo rebuilds the enum when they change
+    let (data, tracked) = match fixture::get_fixture(args) {
+        Some(fixture) => {
+            info!("Loading data from fixture file");
+            let fixture_span = get_str_arg(args, "fixture").map_or(table_span, LitStr::span);
+            let data = fixture.fetch::<M>().await.map_err(|e| syn::Error::new(fixture_span, e))?;
+            let path = fixture.path().to_str().ok_or_else(|| {
+                syn::Error::new(fixture_span, format!("Fixture path {} isn't valid UTF-8", fixture.path().display()))
+            })?;
+            (data, quote! { const _: &[u8] = include_bytes!(#path); })
+        }
+        None => (source.fetch::<M>().await.map_err(|e| syn::Error::new(table_span, e))?, TokenStream::default()),
+    };
+
+    let variant = get_variant::<M>(args)?;
+    // composite primary keys are matched by constructors and TryFrom, so they can only be replaced by enum variants
+    // or by fn calls, that keep the raw value around
+    if primary_keys.len() > 1 {
+        for col in &primary_keys {
+            if let Some(Replacement::Split(..) | Replacement::ActiveEnum(_)) = get_replacement::<M>(*col, args)? {
+                return Err(syn::Error::new(
+                    get_arg_span::<M>(*col, args).unwrap_or(table_span),
+                    format!("Composite primary key {col:?} can only be replaced using type, generate or fn parameters"),
+                ));
+            }
+        }
+    }
+    let digit_prefix = get_str_arg(args, "digit_prefix").map(|s| s.value()).unwrap_or_else(|| String::from("_"));
+    let on_collision = get_collision_policy(args)?;
+    let mut renames = get_renames(args)?;
+    let mut ids = vec![];
+    // reverse lookups, with parameter type and variants of every value, by match pattern
+    let lookups = get_lookups::<M>(args)?;
+    let mut lookup_values = lookups.iter().map(|_| (None, BTreeMap::new())).collect::<Vec<_>>();
+    // already used variant names, with the row they come from, if any
+    let mut variant_names =
+        item.variants.iter().map(|variant| (variant.ident.to_string(), None)).collect::<HashMap<_, _>>();
 
     data.iter().try_for_each(|v| {
         let mut key_s = vec![];
+        let mut id = None;
 
         // scan primary keys
         for k in &primary_keys {
-            let val = v.get(*k);
-            // only string values are accepted
-            if let Value::String(Some(s)) = val {
-                key_s.push(s.to_upper_camel_case());
-
-                // if we have a single primary key, create a method as_str and a counter-trait-impl TryFrom<&str>
-                if primary_keys.len() == 1 {
-                    let key = Ident::new(&s.to_upper_camel_case(), Span::call_site());
-                    let v = Literal::string(s.as_str());
+            match v.get(*k) {
+                Value::String(Some(s)) => key_s.push(*s),
+                // UUID values are accepted only as single primary key
+                Value::Uuid(Some(u)) if cfg!(feature = "uuid") && primary_keys.len() == 1 => {
+                    id = Some(Id::Uuid(u.as_u128()))
+                }
+                // integer values are accepted only as single primary key, they will become discriminants
+                val => match get_integer(&val, table_span)? {
+                    Some(n) if primary_keys.len() == 1 => id = Some(Id::Int(n)),
+                    Some(n) => {
+                        return Err(syn::Error::new(
+                            table_span,
+                            format!(
+                                "Integer primary keys are supported only on single primary key tables, \
+                                found {n} for primary key {k:?}"
+                            ),
+                        ))
+                    }
+                    None => {
+                        return Err(syn::Error::new(
+                            table_span,
+                            format!("Unrecognized value type {val:?} for primary key {k:?}"),
+                        ))
+                    }
+                },
+            }
+        }
 
-                    let (_, method, _) = methods
-                        .entry(String::from("as_str"))
-                        .or_insert_with(|| (quote! { &'static str }, Punctuated::<_, Comma>::new(), false));
-                    method.push(quote! {
-                        #name::#key => #v
-                    });
+        // row description for error messages
+        let row = match id {
+            Some(Id::Int(n)) => n.to_string(),
+            Some(Id::Uuid(u)) => Uuid::from_u128(u).to_string(),
+            None => key_s.join(", "),
+        };
 
-                    let (_, method, _) = methods
-                        .entry(String::from("try_from"))
-                        .or_insert_with(|| (quote! { () }, Punctuated::<_, Comma>::new(), false));
-                    method.push(quote! {
-                        #v => Ok(#name::#key)
-                    });
+        // variant name comes from rename map, if any, otherwise from variant columns, if any, otherwise from primary keys
+        let key_ident = if let Some(ident) = renames.remove(&row) {
+            Ok(ident)
+        } else if !variant.is_empty() {
+            let names = variant
+                .iter()
+                .map(|col| match v.get(*col) {
+                    Value::String(Some(s)) => Ok(*s),
+                    val => match get_integer(&val, table_span)? {
+                        Some(n) => Ok(n.to_string()),
+                        None => Err(syn::Error::new(
+                            table_span,
+                            format!("Unrecognized variant value type {val:?} for field {col:?} of row {row}"),
+                        )),
+                    },
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            ident::to_variant(&names, &digit_prefix)
+        } else if id.is_some() {
+            return Err(syn::Error::new(
+                table_span,
+                format!(
+                    "Integer and UUID primary keys need a variant argument, e.g. #[macro(variant = \"name\")], \
+                    found row {row}"
+                ),
+            ));
+        } else {
+            ident::to_variant(&key_s, &digit_prefix)
+        }
+        .map_err(|e| syn::Error::new(table_span, format!("Invalid variant name for row {row}: {e}")))?;
+
+        // detect variant name collisions
+        let key_ident = match variant_names.get(&key_ident.to_string()) {
+            None => key_ident,
+            Some(other) => match on_collision {
+                CollisionPolicy::Error => {
+                    return Err(syn::Error::new(
+                        table_span,
+                        format!(
+                            "Variant name {key_ident} collides between {} and row \"{row}\", \
+                            use #[macro(on_collision = \"suffix\")] to add a numeric suffix \
+                            or #[macro(rename(Variant = \"{row}\"))] to pick a name",
+                            other
+                                .as_ref()
+                                .map(|other| format!("row \"{other}\""))
+                                .unwrap_or_else(|| String::from("declared variant"))
+                        ),
+                    ))
                 }
-            } else {
-                return Err(syn::Error::new(Span::call_site(), format!("Unrecognized value type {val:?}")));
-            }
+                CollisionPolicy::Suffix => {
+                    let base = key_ident.to_string();
+                    let base = base.trim_start_matches("r#");
+                    (2..)
+                        .map(|n| format!("{base}{n}"))
+                        .find(|name| !variant_names.contains_key(name))
+                        .map(|name| Ident::new(&name, Span::call_site()))
+                        .expect("unbounded range")
+                }
+            },
+        };
+        variant_names.insert(key_ident.to_string(), Some(row.clone()));
+
+        // if we have a single string primary key, create a method as_str and a counter-trait-impl TryFrom<&str>
+        if let [s] = key_s.as_slice() {
+            let v = Literal::string(s.as_str());
+
+            let (_, method, _) = methods
+                .entry(String::from("as_str"))
+                .or_insert_with(|| (quote! { &'static str }, Punctuated::<_, Comma>::new(), false));
+            method.push(quote! {
+                #name::#key_ident => #v
+            });
+
+            let (_, method, _) = methods
+                .entry(String::from("try_from"))
+                .or_insert_with(|| (quote! { () }, Punctuated::<_, Comma>::new(), false));
+            method.push(quote! {
+                #v => Ok(#name::#key_ident)
+            });
         }
-        // push primary keys into enum variants
-        let key_ident = Ident::new(&key_s.join("_"), Span::call_site());
-        item.variants.push(Variant {
-            attrs: vec![],
-            ident: key_ident.clone(),
-            fields: Fields::Unit,
-            discriminant: None,
+
+        // push primary keys into enum variants, integer primary keys become discriminants
+        let discriminant = id.and_then(|id| {
+            ids.push((key_ident.clone(), id));
+            match id {
+                Id::Int(n) => {
+                    let v = Literal::i64_unsuffixed(n);
+                    Some((Token![=](Span::call_site()), parse_quote! { #v }))
+                }
+                Id::Uuid(_) => None,
+            }
         });
+        item.variants.push(Variant { attrs: vec![], ident: key_ident.clone(), fields: Fields::Unit, discriminant });
+
+        // collect reverse lookups, unique lookups can't have repeated values
+        for ((col, s, kind), (signature, values)) in lookups.iter().zip(&mut lookup_values) {
+            let replace = get_replacement::<M>(*col, args)?;
+            let value = get_field::<M>(v, *col, replace.as_ref(), &row, s.span())?;
+            let (t, bytes, patterns) = get_pattern(col, value, replace.as_ref(), &row, &digit_prefix, s.span())?;
+            *signature = Some((t, bytes));
+            // the same piece can be repeated in a split value
+            let mut seen = HashSet::new();
+            for pattern in patterns {
+                let key = pattern.to_string();
+                if !seen.insert(key.clone()) {
+                    continue;
+                }
+                let (_, first, idents) =
+                    values.entry(key.clone()).or_insert_with(|| (pattern, row.clone(), Punctuated::<_, Comma>::new()));
+                if *kind == Lookup::Unique && !idents.is_empty() {
+                    return Err(syn::Error::new(
+                        s.span(),
+                        format!(
+                            "Lookup column {col:?} isn't unique, value {key} is shared by rows \"{first}\" and \"{row}\", \
+                            use #[macro(lookup_many = \"{}\")] for non-unique columns",
+                            s.value()
+                        ),
+                    ));
+                }
+                idents.push(quote! { #name::#key_ident });
+            }
+        }
         // generate constructors for every combination of primary keys
         if primary_keys.len() > 1 {
+            // fn replaced keys get a raw accessor, so keys and TryFrom still deal with strings
+            for (col, s) in primary_keys.iter().zip(&key_s) {
+                if let Some(Replacement::Fn(..)) = get_replacement::<M>(*col, args)? {
+                    let v = Literal::string(s);
+                    let (_, method, _) = methods
+                        .entry(format!("{col:?}_raw"))
+                        .or_insert_with(|| (quote! { &'static str }, Punctuated::<_, Comma>::new(), false));
+                    method.push(quote! {
+                        #name::#key_ident => #v
+                    });
+                }
+            }
+            let key_args = primary_keys
+                .iter()
+                .zip(&key_s)
+                .map(|(col, s)| match get_variant_type(get_replacement::<M>(*col, args)?) {
+                    Some(r) => {
+                        let ident = ident::to_variant(&[s], &digit_prefix).map_err(|e| {
+                            syn::Error::new(
+                                get_arg_span::<M>(*col, args).unwrap_or(table_span),
+                                format!("Invalid variant name for field {col:?} of row {row}: {e}"),
+                            )
+                        })?;
+                        Ok(quote! { <#r>::#ident })
+                    }
+                    // strings are matched as bytes, since const fn can't match &str
+                    None => {
+                        let v = Literal::byte_string(s.as_bytes());
+                        Ok(quote! { #v })
+                    }
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
             for n in 1..=primary_keys.len() {
                 for combo in primary_keys.iter().enumerate().combinations(n) {
                     let cols = combo.iter().map(|(_, col)| **col).collect::<Vec<_>>();
@@ -110,9 +376,9 @@ where
                         .collect::<Vec<_>>()
                         .join("_and_");
                     let key = combo.iter().map(|(index, _)| key_s[*index].clone()).collect::<Vec<_>>();
+                    let values = combo.iter().map(|(index, _)| key_args[*index].clone()).collect::<Vec<_>>();
                     let (_, method) = constructors.entry(method).or_insert_with(|| (cols, HashMap::new()));
-                    let (_, idents) =
-                        method.entry(key.join("_")).or_insert_with(|| (key, Punctuated::<_, Comma>::new()));
+                    let (_, idents) = method.entry(key).or_insert_with(|| (values, Punctuated::<_, Comma>::new()));
                     idents.push(quote! { #name::#key_ident });
                 }
             }
@@ -120,125 +386,20 @@ where
 
         // create a method for every non-primary_key column
         for col in <M as EntityTrait>::Column::iter() {
-            let replace = get_replacement::<M>(col, args);
+            let replace = get_replacement::<M>(col, args)?;
 
-            // skip self-describing methods (would be an as_str clone)
-            if primary_keys.len() == 1 && primary_keys.contains(&col) && replace.is_none() {
+            // skip self-describing methods (would be an as_str or id clone) and opted out columns
+            if (primary_keys.len() == 1 && primary_keys.contains(&col) && (replace.is_none() || id.is_some()))
+                || matches!(replace, Some(Replacement::Skip))
+            {
                 continue;
             }
 
-            // keep only managed data types
-            let (t, value) = match v.get(col) {
-                Value::Bool(b) => (
-                    quote! { bool },
-                    b.map(|b| {
-                        let v = LitBool::new(b, Span::call_site());
-                        quote! { #v }
-                    }),
-                ),
-                Value::TinyInt(n) => (
-                    quote! { i8 },
-                    n.map(|n| {
-                        let v = Literal::i8_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::SmallInt(n) => (
-                    quote! { i16 },
-                    n.map(|n| {
-                        let v = Literal::i16_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::Int(n) => (
-                    quote! { i32 },
-                    n.map(|n| {
-                        let v = Literal::i32_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::BigInt(n) => (
-                    quote! { i64 },
-                    n.map(|n| {
-                        let v = Literal::i64_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::TinyUnsigned(n) => (
-                    quote! { u8 },
-                    n.map(|n| {
-                        let v = Literal::u8_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::SmallUnsigned(n) => (
-                    quote! { u16 },
-                    n.map(|n| {
-                        let v = Literal::u16_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::Unsigned(n) => (
-                    quote! { u32 },
-                    n.map(|n| {
-                        let v = Literal::u32_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::BigUnsigned(n) => (
-                    quote! { u64 },
-                    n.map(|n| {
-                        let v = Literal::u64_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::Float(n) => (
-                    quote! { f32 },
-                    n.map(|n| {
-                        let v = Literal::f32_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::Double(n) => (
-                    quote! { f64 },
-                    n.map(|n| {
-                        let v = Literal::f64_unsuffixed(n);
-                        quote! { #v }
-                    }),
-                ),
-                Value::String(s) => match replace {
-                    Some(Replacement::Type(r)) => (
-                        r.clone(),
-                        s.map(|s| {
-                            let ident = Ident::new(&s.to_upper_camel_case(), Span::call_site());
-                            quote! { #r::#ident }
-                        }),
-                    ),
-                    Some(Replacement::Fn(f, Some(r))) => (
-                        r.clone(),
-                        s.map(|s| {
-                            let v = Literal::string(s.as_str());
-                            quote! { #r::#f(#v) }
-                        }),
-                    ),
-                    Some(Replacement::Fn(_, None)) => {
-                        // teoretically we could accept only a function, but we won't know the return type
-                        return Err(syn::Error::new(
-                            Span::call_site(),
-                            format!("Missing parameter type for field {col:?}"),
-                        ));
-                    }
-                    _ => (
-                        quote! { &'static str },
-                        s.map(|s| {
-                            let v = Literal::string(s.as_str());
-                            quote! { #v }
-                        }),
-                    ),
-                },
-                // disable ChronoDateTime for now, it would only produce methods for created_at and updated_at fields
-                // Value::ChronoDateTime(dt) => (quote! { chrono::NaiveDateTime }, Lit::Verbatim(Literal)),
-                _ => continue,
+            // keep only managed data types, errors point to the column argument, if any
+            let span = get_arg_span::<M>(col, args).unwrap_or(table_span);
+            let value = get_field::<M>(v, col, replace.as_ref(), &row, span)?;
+            let Some((t, value)) = get_value(&col, value, replace.as_ref(), &row, &digit_prefix, span)? else {
+                continue;
             };
             let (_, method, option) =
                 methods.entry(format!("{col:?}")).or_insert_with(|| (t, Punctuated::<_, Comma>::new(), false));
@@ -254,77 +415,185 @@ where
         Ok(())
     })?;
 
+    // every rename must match a row
+    if let Some((key, ident)) = renames.iter().next() {
+        return Err(syn::Error::new(ident.span(), format!("Renamed row \"{key}\" not found")));
+    }
+
     // decorate constructors
-    let constructors = constructors.into_iter().map(|(name, (cols, body))| {
-        let is_full = cols.len() == primary_keys.len();
-        let fn_name = Ident::new(&format!("get_by_{name}"), Span::call_site());
-        let signature = cols
-            .iter()
-            .map(|col| {
-                let field_name = Ident::new(&format!("{col:?}").to_snake_case(), Span::call_site());
-                match get_replacement::<M>(*col, args) {
-                    Some(Replacement::Type(r)) => quote! { #field_name: #r },
-                    _ => quote! { #field_name: &str },
-                }
-            })
-            .collect::<Punctuated<_, Comma>>();
-        let m = cols
-            .iter()
-            .map(|col| {
-                let field_name = Ident::new(&format!("{col:?}").to_snake_case(), Span::call_site());
-                quote! { #field_name }
-            })
-            .collect::<Punctuated<_, Comma>>();
-        let body = body
-            .iter()
-            .map(|(_, (values, array_body))| {
-                let args = cols
-                    .iter()
-                    .enumerate()
-                    .map(|(index, col)| match get_replacement::<M>(*col, args) {
-                        Some(Replacement::Type(r)) => {
-                            let ident = Ident::new(&values[index].to_upper_camel_case(), Span::call_site());
-                            quote! { #r::#ident }
+    let constructors = constructors
+        .into_iter()
+        .map(|(name, (cols, body))| {
+            let is_full = cols.len() == primary_keys.len();
+            let fn_name = Ident::new(&format!("get_by_{name}"), Span::call_site());
+            let (signature, m): (Punctuated<_, Comma>, Punctuated<_, Comma>) = cols
+                .iter()
+                .map(|col| {
+                    let field_name = Ident::new(&format!("{col:?}").to_snake_case(), Span::call_site());
+                    Ok(match get_variant_type(get_replacement::<M>(*col, args)?) {
+                        Some(r) => (quote! { #field_name: #r }, quote! { #field_name }),
+                        None => (quote! { #field_name: &str }, quote! { #field_name.as_bytes() }),
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            let body = body
+                .iter()
+                .map(|(_, (values, array_body))| {
+                    let args = values.iter().collect::<Punctuated<_, Comma>>();
+                    if is_full {
+                        quote! {
+                            (#args,) => Some(#array_body)
                         }
-                        _ => {
-                            let v = Literal::string(values[index].as_str());
-                            quote! { #v }
+                    } else {
+                        quote! {
+                            (#args,) => &[#array_body]
                         }
-                    })
-                    .collect::<Punctuated<_, Comma>>();
-                if is_full {
-                    quote! {
-                        (#args,) => Some(#array_body)
                     }
-                } else {
-                    quote! {
-                        (#args,) => &[#array_body]
+                })
+                .collect::<Punctuated<_, Comma>>();
+            Ok(if is_full {
+                quote! {
+                    pub const fn #fn_name(#signature) -> Option<Self> {
+                        match (#m,) {
+                            #body,
+                            _ => None,
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    pub const fn #fn_name(#signature) -> &'static [Self] {
+                        match (#m,) {
+                            #body,
+                            _ => &[],
+                        }
                     }
                 }
             })
-            .collect::<Punctuated<_, Comma>>();
-        if is_full {
-            quote! {
-                pub const fn #fn_name(#signature) -> Option<Self> {
-                    match (#m,) {
-                        #body,
-                        _ => None,
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // decorate reverse lookups, unique ones return an Option, non-unique ones and groups a slice
+    let lookups = lookups
+        .iter()
+        .zip(lookup_values)
+        .map(|((col, s, kind), (signature, values))| {
+            // without rows, the parameter type comes from a NULL value of the column type
+            let (t, bytes) = match signature {
+                Some(signature) => signature,
+                None => {
+                    let column_type = col.def().get_column_type().clone();
+                    let null = get_column_null(&column_type).ok_or_else(|| {
+                        syn::Error::new(
+                            s.span(),
+                            format!(
+                                "Field {col:?} of column type {column_type:?} can't be used for lookups, \
+                                only string, binary, boolean and integer fields can, even split or in arrays"
+                            ),
+                        )
+                    })?;
+                    let replace = get_replacement::<M>(*col, args)?;
+                    let (t, bytes, _) = get_pattern(col, null, replace.as_ref(), "", &digit_prefix, s.span())?;
+                    (t, bytes)
+                }
+            };
+            let field_name = Ident::new(&format!("{col:?}").to_snake_case(), Span::call_site());
+            let fn_name = match kind {
+                Lookup::Unique | Lookup::Many => Ident::new(&format!("get_by_{field_name}"), Span::call_site()),
+                Lookup::Group => Ident::new(&format!("all_with_{field_name}"), Span::call_site()),
+            };
+            let m = if bytes {
+                quote! { #field_name.as_bytes() }
+            } else {
+                quote! { #field_name }
+            };
+            let (patterns, idents): (Vec<_>, Vec<_>) =
+                values.into_values().map(|(pattern, _, idents)| (pattern, idents)).unzip();
+            Ok(match kind {
+                Lookup::Unique => quote! {
+                    pub const fn #fn_name(#field_name: #t) -> Option<Self> {
+                        match #m {
+                            #(#patterns => Some(#idents),)*
+                            _ => None,
+                        }
+                    }
+                },
+                Lookup::Many | Lookup::Group => quote! {
+                    pub const fn #fn_name(#field_name: #t) -> &'static [Self] {
+                        match #m {
+                            #(#patterns => &[#idents],)*
+                            _ => &[],
+                        }
                     }
+                },
+            })
+        })
+        .collect::<syn::Result<TokenStream>>()?;
+
+    // composite primary keys generate a keys method and a counter-trait-impl TryFrom<(&str, ..)>
+    let (keys, try_from_keys) = if primary_keys.len() > 1 {
+        let (types, getters): (Vec<_>, Vec<_>) = primary_keys
+            .iter()
+            .map(|col| {
+                // fn replaced keys are returned raw
+                let getter = match get_replacement::<M>(*col, args)? {
+                    Some(Replacement::Fn(..)) => format!("{col:?}_raw"),
+                    _ => format!("{col:?}"),
+                };
+                match methods.get(&getter) {
+                    Some((t, _, _)) => Ok((t, Ident::new(&getter.to_snake_case(), Span::call_site()))),
+                    None => Err(syn::Error::new(
+                        get_arg_span::<M>(*col, args).unwrap_or(table_span),
+                        format!("Primary key {col:?} can't be skipped"),
+                    )),
                 }
+            })
+            .collect::<syn::Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let keys = quote! {
+            pub const fn keys(&self) -> (#(#types,)*) {
+                (#(self.#getters(),)*)
             }
-        } else {
-            quote! {
-                pub const fn #fn_name(#signature) -> &'static [Self] {
-                    match (#m,) {
-                        #body,
-                        _ => &[],
-                    }
+        };
+        let accessors = primary_keys
+            .iter()
+            .map(|col| Ident::new(&format!("{col:?}").to_snake_case(), Span::call_site()))
+            .collect::<Vec<_>>();
+
+        let (key_types, parts): (Vec<_>, Vec<_>) = primary_keys
+            .iter()
+            .zip(&accessors)
+            .map(|(col, field_name)| {
+                Ok(match get_variant_type(get_replacement::<M>(*col, args)?) {
+                    Some(r) => (r, quote! { format!("{:?}", #field_name) }),
+                    None => (quote! { &'a str }, quote! { String::from(#field_name) }),
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let fn_name = Ident::new(&format!("get_by_{}", accessors.iter().join("_and_")), Span::call_site());
+        let try_from_keys = quote! {
+            impl<'a> TryFrom<(#(#key_types,)*)> for #name {
+                type Error = String;
+                fn try_from((#(#accessors,)*): (#(#key_types,)*)) -> Result<Self, Self::Error> {
+                    // enum keys aren't required to be Copy, so they're described before being moved
+                    let keys = [#(#parts),*];
+                    Self::#fn_name(#(#accessors),*)
+                        .ok_or_else(|| format!("Unknown {} ({})", stringify!(#name), keys.join(", ")))
                 }
             }
-        }
-    });
+        };
+        (keys, try_from_keys)
+    } else {
+        Default::default()
+    };
 
     // separate try_from from other methods
+    let has_str_key = methods.contains_key("try_from");
     let try_from = methods
         .remove("try_from")
         .map(|(_, matches, _)| {
@@ -342,6 +611,130 @@ where
         })
         .unwrap_or_default();
 
+    // opt-in trait implementations, built over as_str and TryFrom<&str>
+    let derives = [
+        (
+            "derive_display",
+            quote! {
+                impl ::std::fmt::Display for #name {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        f.write_str(self.as_str())
+                    }
+                }
+            },
+        ),
+        (
+            "derive_from_str",
+            quote! {
+                impl ::std::str::FromStr for #name {
+                    type Err = String;
+                    fn from_str(s: &str) -> Result<Self, Self::Err> {
+                        Self::try_from(s)
+                    }
+                }
+            },
+        ),
+        (
+            "derive_serde",
+            quote! {
+                impl ::serde::Serialize for #name {
+                    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.serialize_str(self.as_str())
+                    }
+                }
+
+                impl<'de> ::serde::Deserialize<'de> for #name {
+                    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                        let s = <::std::borrow::Cow<'de, str> as ::serde::Deserialize>::deserialize(deserializer)?;
+                        Self::try_from(s.as_ref()).map_err(::serde::de::Error::custom)
+                    }
+                }
+            },
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(flag, tokens)| get_flag(args, flag).map(|path| (path, tokens)))
+    .map(|(path, tokens)| {
+        if has_str_key {
+            Ok(tokens)
+        } else {
+            Err(syn::Error::new_spanned(path, "Only enums with a single string primary key can derive traits"))
+        }
+    })
+    .collect::<syn::Result<TokenStream>>()?;
+
+    // integer primary key generates an id method and a counter-trait-impl TryFrom<i64>
+    // UUID primary key generates an id method and a counter-trait-impl TryFrom<Uuid>
+    let try_from_id = match ids.first() {
+        None => TokenStream::default(),
+        Some((_, Id::Int(_))) => {
+            let ints = ids
+                .iter()
+                .filter_map(|(ident, id)| match id {
+                    Id::Int(n) => Some((ident, *n)),
+                    Id::Uuid(_) => None,
+                })
+                .collect::<Vec<_>>();
+            let repr = match get_repr(item) {
+                Some(repr) => repr,
+                None => {
+                    let repr = Ident::new(get_int_type(ints.iter().map(|(_, n)| *n)), Span::call_site());
+                    // non-integer representations, like #[repr(C)], conflict with integer ones
+                    if !item.attrs.iter().any(|attr| attr.path.is_ident("repr")) {
+                        item.attrs.push(parse_quote! { #[repr(#repr)] });
+                    }
+                    repr
+                }
+            };
+            let (ids, id_matches): (Punctuated<_, Comma>, Punctuated<_, Comma>) = ints
+                .iter()
+                .map(|(ident, n)| {
+                    let v = Literal::i64_unsuffixed(*n);
+                    (quote! { #name::#ident => #v }, quote! { #v => Ok(#name::#ident) })
+                })
+                .unzip();
+            methods.insert(String::from("id"), (quote! { #repr }, ids, false));
+            quote! {
+                impl TryFrom<i64> for #name {
+                    type Error = String;
+                    fn try_from(id: i64) -> Result<Self, Self::Error> {
+                        match id {
+                            #id_matches,
+                            _ => Err(format!("Unknown {} {}", stringify!(#name), id)),
+                        }
+                    }
+                }
+            }
+        }
+        Some((_, Id::Uuid(_))) => {
+            let (ids, id_matches): (Punctuated<_, Comma>, Punctuated<_, Comma>) = ids
+                .iter()
+                .filter_map(|(ident, id)| match id {
+                    Id::Uuid(u) => {
+                        let v = LitInt::new(&format!("0x{u:032x}"), Span::call_site());
+                        Some((
+                            quote! { #name::#ident => ::uuid::Uuid::from_u128(#v) },
+                            quote! { #v => Ok(#name::#ident) },
+                        ))
+                    }
+                    Id::Int(_) => None,
+                })
+                .unzip();
+            methods.insert(String::from("id"), (quote! { ::uuid::Uuid }, ids, false));
+            quote! {
+                impl TryFrom<::uuid::Uuid> for #name {
+                    type Error = String;
+                    fn try_from(id: ::uuid::Uuid) -> Result<Self, Self::Error> {
+                        match id.as_u128() {
+                            #id_matches,
+                            _ => Err(format!("Unknown {} {}", stringify!(#name), id)),
+                        }
+                    }
+                }
+            }
+        }
+    };
+
     // decorate methods
     let methods: TokenStream = methods
         .into_iter()
@@ -377,27 +770,1002 @@ where
         .chain(constructors)
         .collect();
 
+    // generate companion enums from distinct field values, fields generating the same enum share it
+    let mut generated: Vec<(Ident, Vec<_>)> = vec![];
+    for col in <M as EntityTrait>::Column::iter() {
+        let (r, sep) = match get_replacement::<M>(col, args)? {
+            Some(Replacement::Generate(r)) => (r, None),
+            Some(Replacement::Split(sep, Some(r))) => match *r {
+                Replacement::Generate(r) => (r, Some(sep)),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        match generated.iter_mut().find(|(other, _)| *other == r) {
+            Some((_, fields)) => fields.push((col, sep)),
+            None => generated.push((r, vec![(col, sep)])),
+        }
+    }
+    let companions = generated
+        .iter()
+        .map(|(r, fields)| companion::<M>(fields, &item.vis, r, &data, &digit_prefix))
+        .collect::<syn::Result<TokenStream>>()?;
+
+    // every unit variant, in declaration order
+    let all =
+        item.variants.iter().filter(|variant| matches!(variant.fields, Fields::Unit)).map(|variant| &variant.ident);
+
     // output result
     Ok(quote! {
         #item
 
+        #tracked
+
         impl #name {
+            pub const ALL: &'static [Self] = &[#(Self::#all),*];
+
+            pub const COUNT: usize = Self::ALL.len();
+
+            pub fn iter() -> ::std::slice::Iter<'static, Self> {
+                Self::ALL.iter()
+            }
+
+            #keys
+
             #methods
+
+            #lookups
         }
 
         #try_from
+
+        #try_from_keys
+
+        #derives
+
+        #try_from_id
+
+        #companions
     })
 }
 
+/// Companion enum facility
+/// Builds an enum with a variant for every distinct value of given fields, with as_str method and TryFrom<&str> implementation
+fn companion<M>(
+    fields: &[(M::Column, Option<String>)],
+    vis: &Visibility,
+    r: &Ident,
+    data: &[M::Model],
+    digit_prefix: &str,
+) -> syn::Result<TokenStream>
+where
+    M: EntityTrait,
+{
+    // distinct values, sorted, and the value every variant name comes from
+    let mut values = BTreeMap::new();
+    let mut variant_names = HashMap::new();
+    for (v, (col, sep)) in data.iter().cartesian_product(fields) {
+        let value = v.get(*col);
+        let strings = match &value {
+            Value::String(Some(s)) => vec![s.as_str()],
+            #[cfg(feature = "postgres-array")]
+            Value::Array(_, Some(a)) => a
+                .iter()
+                .filter_map(|v| match v {
+                    Value::String(Some(s)) => Some(s.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+        for s in strings {
+            let pieces = match sep {
+                Some(sep) => s.split(sep.as_str()).filter(|piece| !piece.is_empty()).collect(),
+                None => vec![s],
+            };
+            for piece in pieces {
+                if values.contains_key(piece) {
+                    continue;
+                }
+                let ident = ident::to_variant(&[piece], digit_prefix).map_err(|e| {
+                    syn::Error::new(
+                        r.span(),
+                        format!("Invalid variant name for {r} value \"{piece}\" of field {col:?}: {e}"),
+                    )
+                })?;
+                if let Some(other) = variant_names.insert(ident.to_string(), piece.to_owned()) {
+                    return Err(syn::Error::new(
+                        r.span(),
+                        format!("Variant name {r}::{ident} collides between values \"{other}\" and \"{piece}\""),
+                    ));
+                }
+                values.insert(piece.to_owned(), ident);
+            }
+        }
+    }
+
+    let (as_str, try_from): (Vec<_>, Vec<_>) = values
+        .iter()
+        .map(|(value, ident)| {
+            let v = Literal::string(value);
+            (quote! { #r::#ident => #v }, quote! { #v => Ok(#r::#ident) })
+        })
+        .unzip();
+    let variants = values.values();
+    Ok(quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #r {
+            #(#variants),*
+        }
+
+        impl #r {
+            pub const fn as_str(&self) -> &'static str {
+                match *self {
+                    #(#as_str,)*
+                }
+            }
+        }
+
+        impl<'a> TryFrom<&'a str> for #r {
+            type Error = String;
+            fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+                match s {
+                    #(#try_from,)*
+                    _ => Err(format!("Unknown {} {}", stringify!(#r), s)),
+                }
+            }
+        }
+    })
+}
+
+/// Maps given value to its type and const expression, if the value type is supported
+/// Value is `None` on NULL values
+fn get_value<C: Debug>(
+    col: &C,
+    value: Value,
+    replace: Option<&Replacement>,
+    row: &str,
+    digit_prefix: &str,
+    span: Span,
+) -> syn::Result<Option<(TokenStream, Option<TokenStream>)>> {
+    let (t, value) = match value {
+        Value::Bool(b) => (
+            quote! { bool },
+            b.map(|b| {
+                let v = LitBool::new(b, Span::call_site());
+                quote! { #v }
+            }),
+        ),
+        Value::TinyInt(n) => (
+            quote! { i8 },
+            n.map(|n| {
+                let v = Literal::i8_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::SmallInt(n) => (
+            quote! { i16 },
+            n.map(|n| {
+                let v = Literal::i16_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::Int(n) => (
+            quote! { i32 },
+            n.map(|n| {
+                let v = Literal::i32_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::BigInt(n) => (
+            quote! { i64 },
+            n.map(|n| {
+                let v = Literal::i64_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::TinyUnsigned(n) => (
+            quote! { u8 },
+            n.map(|n| {
+                let v = Literal::u8_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::SmallUnsigned(n) => (
+            quote! { u16 },
+            n.map(|n| {
+                let v = Literal::u16_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::Unsigned(n) => (
+            quote! { u32 },
+            n.map(|n| {
+                let v = Literal::u32_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::BigUnsigned(n) => (
+            quote! { u64 },
+            n.map(|n| {
+                let v = Literal::u64_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::Float(n) => (
+            quote! { f32 },
+            n.map(|n| {
+                let v = Literal::f32_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::Double(n) => (
+            quote! { f64 },
+            n.map(|n| {
+                let v = Literal::f64_unsuffixed(n);
+                quote! { #v }
+            }),
+        ),
+        Value::String(s) => match replace {
+            Some(Replacement::Type(r)) => {
+                let ident = s.map(|s| ident::to_variant(&[s.as_str()], digit_prefix)).transpose().map_err(|e| {
+                    syn::Error::new(span, format!("Invalid variant name for field {col:?} of row {row}: {e}"))
+                })?;
+                return Ok(Some((quote! { #r }, ident.map(|ident| quote! { <#r>::#ident }))));
+            }
+            // ActiveEnum values are already variant names
+            Some(Replacement::ActiveEnum(r)) => {
+                let ident = s.map(|s| Ident::new(&s, Span::call_site()));
+                return Ok(Some((quote! { #r }, ident.map(|ident| quote! { <#r>::#ident }))));
+            }
+            // generated enums are basic replacements
+            Some(Replacement::Generate(r)) => {
+                let replace = Replacement::Type(parse_quote! { #r });
+                return get_value(col, Value::String(s), Some(&replace), row, digit_prefix, span);
+            }
+            Some(Replacement::Split(sep, replace)) => {
+                // every non-empty piece is mapped on its own, as it was a string value
+                let replace = replace.as_deref();
+                let Some((t, _)) = get_value(col, Value::String(None), replace, row, digit_prefix, span)? else {
+                    return Ok(None);
+                };
+                let values = s
+                    .map(|s| {
+                        s.split(sep.as_str())
+                            .filter(|piece| !piece.is_empty())
+                            .map(|piece| {
+                                let piece = Value::String(Some(Box::new(piece.to_owned())));
+                                Ok(get_value(col, piece, replace, row, digit_prefix, span)?.and_then(|(_, v)| v))
+                            })
+                            .filter_map(syn::Result::transpose)
+                            .collect::<syn::Result<Vec<_>>>()
+                    })
+                    .transpose()?;
+                return Ok(Some((quote! { &'static [#t] }, values.map(|values| slice(&t, &values)))));
+            }
+            _ => (
+                quote! { &'static str },
+                s.map(|s| {
+                    let v = Literal::string(s.as_str());
+                    quote! { #v }
+                }),
+            ),
+        },
+        #[cfg(feature = "chrono")]
+        Value::ChronoDate(d) => (quote! { ::chrono::NaiveDate }, d.map(|d| chrono_date(&d))),
+        #[cfg(feature = "chrono")]
+        Value::ChronoTime(t) => (quote! { ::chrono::NaiveTime }, t.map(|t| chrono_time(&t))),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTime(dt) => (quote! { ::chrono::NaiveDateTime }, dt.map(|dt| chrono_date_time(&dt))),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeUtc(dt) => (
+            quote! { ::chrono::DateTime<::chrono::Utc> },
+            dt.map(|dt| {
+                let v = chrono_date_time(&dt.naive_utc());
+                quote! { ::chrono::DateTime::<::chrono::Utc>::from_naive_utc_and_offset(#v, ::chrono::Utc) }
+            }),
+        ),
+        #[cfg(feature = "rust_decimal")]
+        Value::Decimal(d) => (quote! { ::rust_decimal::Decimal }, d.map(|d| decimal(&d))),
+        #[cfg(feature = "rust_decimal")]
+        Value::BigDecimal(d) => (
+            quote! { ::rust_decimal::Decimal },
+            d.map(|d| {
+                let s = d.to_string();
+                rust_decimal::Decimal::from_str_exact(&s)
+                    .or_else(|_| rust_decimal::Decimal::from_scientific(&s))
+                    .map(|d| decimal(&d))
+                    .map_err(|e| {
+                        syn::Error::new(span, format!("Invalid decimal {s} for field {col:?} of row {row}: {e}"))
+                    })
+            })
+            .transpose()?,
+        ),
+        Value::Json(j) => (quote! { ::symbols_models::Json }, j.map(|j| json(&j))),
+        Value::Bytes(b) => (
+            quote! { &'static [u8] },
+            b.map(|b| {
+                let v = Literal::byte_string(&b);
+                quote! { #v }
+            }),
+        ),
+        Value::Uuid(u) if cfg!(feature = "uuid") => (
+            quote! { ::uuid::Uuid },
+            u.map(|u| {
+                let v = LitInt::new(&format!("0x{:032x}", u.as_u128()), Span::call_site());
+                quote! { ::uuid::Uuid::from_u128(#v) }
+            }),
+        ),
+        #[cfg(feature = "postgres-array")]
+        Value::Array(ty, a) => {
+            // element type is mapped from a null value of the same type
+            let Some((t, _)) = get_array_null(&ty)
+                .map(|null| get_value(col, null, replace, row, digit_prefix, span))
+                .transpose()?
+                .flatten()
+            else {
+                return Ok(None);
+            };
+            let values = a
+                .map(|a| {
+                    a.into_iter()
+                        .map(|v| match get_value(col, v, replace, row, digit_prefix, span)? {
+                            Some((_, Some(v))) => Ok(v),
+                            _ => Err(syn::Error::new(
+                                span,
+                                format!("Unsupported NULL array element for field {col:?} of row {row}"),
+                            )),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()
+                })
+                .transpose()?;
+            // replacements are already applied to elements
+            return Ok(Some((quote! { &'static [#t] }, values.map(|values| slice(&t, &values)))));
+        }
+        _ => return Ok(None),
+    };
+
+    // any other replacement is a call to a const fn with the value as parameter
+    let t_name = || type_name(&t);
+    match replace {
+        Some(Replacement::Fn(f, r)) => Ok(Some((quote! { #r }, value.map(|v| call(f, r, v))))),
+        Some(Replacement::Split(..)) => Err(syn::Error::new(
+            span,
+            format!("Field {col:?} of type {} can't be split, only string fields can", t_name()),
+        )),
+        Some(Replacement::Type(_)) => Err(syn::Error::new(
+            span,
+            format!("Field {col:?} of type {} can only be replaced using both type and fn parameters", t_name()),
+        )),
+        Some(Replacement::Generate(_)) => Err(syn::Error::new(
+            span,
+            format!("Field {col:?} of type {} can't generate an enum, only string fields can", t_name()),
+        )),
+        Some(Replacement::ActiveEnum(_)) => {
+            Err(syn::Error::new(span, format!("Field {col:?} of type {} isn't an ActiveEnum", t_name())))
+        }
+        _ => Ok(Some((t, value))),
+    }
+}
+
+/// Maps given value to parameter type and match patterns of lookup methods, with a flag telling the parameter is matched as bytes
+/// Split and array values have a pattern for every piece, NULL values have none
+fn get_pattern<C: Debug>(
+    col: &C,
+    value: Value,
+    replace: Option<&Replacement>,
+    row: &str,
+    digit_prefix: &str,
+    span: Span,
+) -> syn::Result<(TokenStream, bool, Vec<TokenStream>)> {
+    match (value, replace) {
+        (Value::String(s), Some(Replacement::Split(sep, replace))) => {
+            // every non-empty piece is mapped on its own, as it was a string value
+            let replace = replace.as_deref();
+            let (t, bytes, _) = get_pattern(col, Value::String(None), replace, row, digit_prefix, span)?;
+            let mut patterns = vec![];
+            for piece in s.iter().flat_map(|s| s.split(sep.as_str())).filter(|piece| !piece.is_empty()) {
+                let piece = Value::String(Some(Box::new(piece.to_owned())));
+                patterns.extend(get_pattern(col, piece, replace, row, digit_prefix, span)?.2);
+            }
+            Ok((t, bytes, patterns))
+        }
+        // enum variants are valid patterns
+        (
+            value @ Value::String(_),
+            Some(replace @ (Replacement::Type(_) | Replacement::Generate(_) | Replacement::ActiveEnum(_))),
+        ) => {
+            let (t, pattern) = get_value(col, value, Some(replace), row, digit_prefix, span)?
+                .expect("string values are always supported");
+            Ok((t, false, pattern.into_iter().collect()))
+        }
+        // strings are matched as bytes, since const fn can't match &str
+        (Value::String(s), _) => Ok((
+            quote! { &str },
+            true,
+            s.into_iter()
+                .map(|s| {
+                    let v = Literal::byte_string(s.as_bytes());
+                    quote! { #v }
+                })
+                .collect(),
+        )),
+        (value, Some(Replacement::Split(..))) => Err(syn::Error::new(
+            span,
+            format!("Field {col:?} of type {value:?} can't be split, only string fields can"),
+        )),
+        (Value::Bytes(b), _) => Ok((
+            quote! { &[u8] },
+            false,
+            b.into_iter()
+                .map(|b| {
+                    let v = Literal::byte_string(&b);
+                    quote! { #v }
+                })
+                .collect(),
+        )),
+        (
+            value @ (Value::Bool(_)
+            | Value::TinyInt(_)
+            | Value::SmallInt(_)
+            | Value::Int(_)
+            | Value::BigInt(_)
+            | Value::TinyUnsigned(_)
+            | Value::SmallUnsigned(_)
+            | Value::Unsigned(_)
+            | Value::BigUnsigned(_)),
+            _,
+        ) => {
+            let (t, pattern) =
+                get_value(col, value, None, row, digit_prefix, span)?.expect("integer values are always supported");
+            Ok((t, false, pattern.into_iter().collect()))
+        }
+        // element type is mapped from a null value of the same type
+        #[cfg(feature = "postgres-array")]
+        (Value::Array(ty, a), replace) if get_array_null(&ty).is_some() => {
+            let null = get_array_null(&ty).expect("checked by guard");
+            let (t, bytes, _) = get_pattern(col, null, replace, row, digit_prefix, span)?;
+            let mut patterns = vec![];
+            for v in a.into_iter().flat_map(|a| *a) {
+                patterns.extend(get_pattern(col, v, replace, row, digit_prefix, span)?.2);
+            }
+            Ok((t, bytes, patterns))
+        }
+        (value, _) => Err(syn::Error::new(
+            span,
+            format!(
+                "Field {col:?} of type {value:?} can't be used for lookups, \
+                only string, binary, boolean and integer fields can, even split or in arrays"
+            ),
+        )),
+    }
+}
+
+/// Type of primary key values replaced by enum variants, if any
+fn get_variant_type(replace: Option<Replacement>) -> Option<TokenStream> {
+    match replace {
+        Some(Replacement::Type(r)) => Some(quote! { #r }),
+        Some(Replacement::Generate(r)) => Some(quote! { #r }),
+        _ => None,
+    }
+}
+
+/// Const expression building a static slice
+/// Values can be const fn calls, that aren't promoted to static, so the slice is wrapped in a const
+fn slice(t: &TokenStream, values: &[TokenStream]) -> TokenStream {
+    quote! { { const VALUES: &[#t] = &[#(#values),*]; VALUES } }
+}
+
+/// Type name as written in code, without the spacing of token streams
+fn type_name(t: &TokenStream) -> String {
+    let mut name = String::new();
+    for token in t.clone() {
+        // spaces only separate words, e.g. `dyn Trait` or `&'static [u8]`
+        let word = name.ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        match token {
+            TokenTree::Ident(ident) => {
+                if word {
+                    name.push(' ');
+                }
+                name.push_str(&ident.to_string());
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                if word && !open.is_empty() {
+                    name.push(' ');
+                }
+                name.push_str(open);
+                name.push_str(&type_name(&group.stream()));
+                name.push_str(close);
+            }
+            TokenTree::Punct(punct) => {
+                name.push(punct.as_char());
+                if punct.as_char() == ',' {
+                    name.push(' ');
+                }
+            }
+            TokenTree::Literal(literal) => name.push_str(&literal.to_string()),
+        }
+    }
+    name
+}
+
+/// Null value of given column type, if the column type can be used for lookups
+fn get_column_null(t: &ColumnType) -> Option<Value> {
+    Some(match t {
+        ColumnType::Char(_) | ColumnType::String(_) | ColumnType::Text | ColumnType::Enum { .. } => Value::String(None),
+        ColumnType::Binary(_) | ColumnType::VarBinary(_) | ColumnType::Blob => Value::Bytes(None),
+        ColumnType::Boolean => Value::Bool(None),
+        ColumnType::TinyInteger => Value::TinyInt(None),
+        ColumnType::SmallInteger => Value::SmallInt(None),
+        ColumnType::Integer => Value::Int(None),
+        ColumnType::BigInteger => Value::BigInt(None),
+        ColumnType::TinyUnsigned => Value::TinyUnsigned(None),
+        ColumnType::SmallUnsigned => Value::SmallUnsigned(None),
+        ColumnType::Unsigned => Value::Unsigned(None),
+        ColumnType::BigUnsigned => Value::BigUnsigned(None),
+        #[cfg(feature = "postgres-array")]
+        ColumnType::Array(t) => {
+            let ty = match get_column_null(t)? {
+                Value::String(_) => ArrayType::String,
+                Value::Bytes(_) => ArrayType::Bytes,
+                Value::Bool(_) => ArrayType::Bool,
+                Value::TinyInt(_) => ArrayType::TinyInt,
+                Value::SmallInt(_) => ArrayType::SmallInt,
+                Value::Int(_) => ArrayType::Int,
+                Value::BigInt(_) => ArrayType::BigInt,
+                Value::TinyUnsigned(_) => ArrayType::TinyUnsigned,
+                Value::SmallUnsigned(_) => ArrayType::SmallUnsigned,
+                Value::Unsigned(_) => ArrayType::Unsigned,
+                Value::BigUnsigned(_) => ArrayType::BigUnsigned,
+                _ => return None,
+            };
+            Value::Array(ty, None)
+        }
+        _ => return None,
+    })
+}
+
+/// Null value of given array element type, if the element type is supported
+#[cfg(feature = "postgres-array")]
+fn get_array_null(ty: &ArrayType) -> Option<Value> {
+    Some(match ty {
+        ArrayType::Bool => Value::Bool(None),
+        ArrayType::TinyInt => Value::TinyInt(None),
+        ArrayType::SmallInt => Value::SmallInt(None),
+        ArrayType::Int => Value::Int(None),
+        ArrayType::BigInt => Value::BigInt(None),
+        ArrayType::TinyUnsigned => Value::TinyUnsigned(None),
+        ArrayType::SmallUnsigned => Value::SmallUnsigned(None),
+        ArrayType::Unsigned => Value::Unsigned(None),
+        ArrayType::BigUnsigned => Value::BigUnsigned(None),
+        ArrayType::Float => Value::Float(None),
+        ArrayType::Double => Value::Double(None),
+        ArrayType::String => Value::String(None),
+        ArrayType::Bytes => Value::Bytes(None),
+        ArrayType::Json => Value::Json(None),
+        ArrayType::ChronoDate => Value::ChronoDate(None),
+        ArrayType::ChronoTime => Value::ChronoTime(None),
+        ArrayType::ChronoDateTime => Value::ChronoDateTime(None),
+        ArrayType::ChronoDateTimeUtc => Value::ChronoDateTimeUtc(None),
+        ArrayType::Decimal => Value::Decimal(None),
+        ArrayType::BigDecimal => Value::BigDecimal(None),
+        ArrayType::Uuid => Value::Uuid(None),
+        _ => return None,
+    })
+}
+
+/// Const expression building given JSON value as a `symbols_models::Json` tree
+fn json(v: &JsonValue) -> TokenStream {
+    match v {
+        JsonValue::Null => quote! { ::symbols_models::Json::Null },
+        JsonValue::Bool(b) => quote! { ::symbols_models::Json::Bool(#b) },
+        JsonValue::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                quote! { ::symbols_models::Json::Int(#i) }
+            } else if let Some(u) = n.as_u64() {
+                quote! { ::symbols_models::Json::UInt(#u) }
+            } else {
+                let f = Literal::f64_unsuffixed(n.as_f64().unwrap_or_default());
+                quote! { ::symbols_models::Json::Float(#f) }
+            }
+        }
+        JsonValue::String(s) => quote! { ::symbols_models::Json::String(#s) },
+        JsonValue::Array(a) => {
+            let items = a.iter().map(json);
+            quote! { ::symbols_models::Json::Array(&[#(#items),*]) }
+        }
+        JsonValue::Object(o) => {
+            let items = o.iter().map(|(k, v)| {
+                let v = json(v);
+                quote! { (#k, #v) }
+            });
+            quote! { ::symbols_models::Json::Object(&[#(#items),*]) }
+        }
+    }
+}
+
+/// Non-string single primary key values
+#[derive(Clone, Copy)]
+enum Id {
+    Int(i64),
+    Uuid(u128),
+}
+
+/// Macro arguments, other than column names
+const ARGS: &[&str] = &[
+    "table",
+    "fixture",
+    "variant",
+    "lookup",
+    "lookup_many",
+    "group",
+    "digit_prefix",
+    "on_collision",
+    "rename",
+    "derive_display",
+    "derive_from_str",
+    "derive_serde",
+];
+
+/// Column arguments, in the form #[macro(field(name = "value"))]
+const FIELD_ARGS: &[&str] = &["type", "fn", "split", "generate", "active_enum", "skip"];
+
+/// Checks every macro argument names a known argument or a column, with column arguments checked as well
+fn validate_args<M>(args: &[NestedMeta]) -> syn::Result<()>
+where
+    M: EntityTrait,
+{
+    let columns = <M as EntityTrait>::Column::iter().map(|col| format!("{col:?}")).collect::<Vec<_>>();
+    let is_column = |name: &str| columns.iter().any(|col| name == col || name == col.to_snake_case());
+    let candidates = ARGS.iter().map(|arg| String::from(*arg)).chain(columns.iter().map(|col| col.to_snake_case()));
+
+    args.iter()
+        .filter_map(|arg| {
+            let meta = match arg {
+                NestedMeta::Meta(meta) => meta,
+                NestedMeta::Lit(lit) => return Some(syn::Error::new_spanned(lit, "Unexpected literal argument")),
+            };
+            let name = meta.path().get_ident().map(Ident::to_string).unwrap_or_default();
+            if ARGS.contains(&name.as_str()) {
+                return None;
+            }
+            if !is_column(&name) {
+                return Some(unknown_arg(meta.path(), &name, candidates.clone()));
+            }
+            // column arguments
+            match meta {
+                Meta::List(ml) => ml
+                    .nested
+                    .iter()
+                    .filter_map(|nested| match nested {
+                        NestedMeta::Meta(meta) => {
+                            let name = meta.path().get_ident().map(Ident::to_string).unwrap_or_default();
+                            (!FIELD_ARGS.contains(&name.as_str())).then(|| {
+                                unknown_arg(meta.path(), &name, FIELD_ARGS.iter().map(|arg| String::from(*arg)))
+                            })
+                        }
+                        NestedMeta::Lit(lit) => Some(syn::Error::new_spanned(lit, "Unexpected literal argument")),
+                    })
+                    .reduce(|mut acc, e| {
+                        acc.combine(e);
+                        acc
+                    }),
+                _ => None,
+            }
+        })
+        .reduce(|mut acc, e| {
+            acc.combine(e);
+            acc
+        })
+        .map_or(Ok(()), Err)
+}
+
+/// Unknown argument error, suggesting the most similar candidate, if any is similar enough
+fn unknown_arg(path: &Path, name: &str, candidates: impl Iterator<Item = String>) -> syn::Error {
+    let suggestion = candidates
+        // transpositions count as a single edit, so `tpye` still suggests `type`
+        .map(|candidate| (strsim::osa_distance(name, &candidate), candidate))
+        .filter(|(distance, _)| *distance <= (name.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance);
+    match suggestion {
+        Some((_, candidate)) => {
+            syn::Error::new_spanned(path, format!("Unknown argument {name}, did you mean {candidate}?"))
+        }
+        None => syn::Error::new_spanned(path, format!("Unknown argument {name}")),
+    }
+}
+
+/// Searches for the span of the argument naming given column, in the form #[macro(field = "enum")] or #[macro(field(..))]
+fn get_arg_span<M>(col: M::Column, args: &[NestedMeta]) -> Option<Span>
+where
+    M: EntityTrait,
+{
+    let col_name = format!("{col:?}");
+    let field_name = col_name.to_snake_case();
+    args.iter().find_map(|arg| match arg {
+        NestedMeta::Meta(meta) if meta.path().is_ident(&col_name) || meta.path().is_ident(&field_name) => {
+            Some(meta.span())
+        }
+        _ => None,
+    })
+}
+
+/// Searches for a string argument, in the form #[macro(name = "value")]
+fn get_str_arg<'a>(args: &'a [NestedMeta], name: &str) -> Option<&'a LitStr> {
+    args.iter().find_map(|arg| match arg {
+        NestedMeta::Meta(Meta::NameValue(mv)) if mv.path.is_ident(name) => match &mv.lit {
+            Lit::Str(s) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Searches for a flag argument, in the form #[macro(name)]
+fn get_flag<'a>(args: &'a [NestedMeta], name: &str) -> Option<&'a Path> {
+    args.iter().find_map(|arg| match arg {
+        NestedMeta::Meta(Meta::Path(p)) if p.is_ident(name) => Some(p),
+        _ => None,
+    })
+}
+
+/// Policies to resolve variant name collisions
+#[derive(Clone, Copy)]
+enum CollisionPolicy {
+    Error,
+    Suffix,
+}
+
+/// Searches for collision policy between macro arguments, in the form #[macro(on_collision = "suffix")]
+fn get_collision_policy(args: &[NestedMeta]) -> syn::Result<CollisionPolicy> {
+    match get_str_arg(args, "on_collision") {
+        None => Ok(CollisionPolicy::Error),
+        Some(s) => match s.value().as_str() {
+            "error" => Ok(CollisionPolicy::Error),
+            "suffix" => Ok(CollisionPolicy::Suffix),
+            other => Err(syn::Error::new(
+                s.span(),
+                format!("Unknown collision policy {other}, expected one of error or suffix"),
+            )),
+        },
+    }
+}
+
+/// Rename map facility
+/// Searches between macro arguments for explicit variant names, in the form #[macro(rename(Variant = "key"))]
+/// where `key` is the primary key value (primary key values joined by `, ` when there are multiple primary keys)
+fn get_renames(args: &[NestedMeta]) -> syn::Result<HashMap<String, Ident>> {
+    args.iter()
+        .filter_map(|arg| match arg {
+            NestedMeta::Meta(Meta::List(ml)) if ml.path.is_ident("rename") => Some(&ml.nested),
+            _ => None,
+        })
+        .flatten()
+        .map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(mv)) => match (mv.path.get_ident(), &mv.lit) {
+                (Some(ident), Lit::Str(s)) => Ok((s.value(), ident.clone())),
+                _ => Err(syn::Error::new_spanned(mv, "Rename must be in the form Variant = \"key\"")),
+            },
+            _ => Err(syn::Error::new_spanned(nested, "Rename must be in the form Variant = \"key\"")),
+        })
+        .collect()
+}
+
+/// Column list facility
+/// Searches between macro arguments for a list of columns, in the form #[macro(name = "column")] or #[macro(name("column1", "column2"))]
+fn get_columns<'a, M>(args: &'a [NestedMeta], name: &str) -> syn::Result<Vec<(M::Column, &'a LitStr)>>
+where
+    M: EntityTrait,
+{
+    let lits = args
+        .iter()
+        .find_map(|arg| match arg {
+            NestedMeta::Meta(Meta::NameValue(mv)) if mv.path.is_ident(name) => Some(vec![&mv.lit]),
+            NestedMeta::Meta(Meta::List(ml)) if ml.path.is_ident(name) => Some(
+                ml.nested
+                    .iter()
+                    .filter_map(|nested| match nested {
+                        NestedMeta::Lit(lit) => Some(lit),
+                        NestedMeta::Meta(_) => None,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default();
+    lits.into_iter()
+        .map(|lit| match lit {
+            Lit::Str(s) => <M as EntityTrait>::Column::iter()
+                .find(|col| {
+                    let col_name = format!("{col:?}");
+                    s.value() == col_name || s.value() == col_name.to_snake_case()
+                })
+                .map(|col| (col, s))
+                .ok_or_else(|| syn::Error::new(s.span(), format!("Unknown {name} column {}", s.value()))),
+            _ => Err(syn::Error::new_spanned(lit, format!("Columns of argument {name} must be strings"))),
+        })
+        .collect()
+}
+
+/// Variant columns facility
+/// Searches between macro arguments for the columns variant names are taken from,
+/// in the form #[macro(variant = "column")] or #[macro(variant("column1", "column2"))]
+fn get_variant<M>(args: &[NestedMeta]) -> syn::Result<Vec<M::Column>>
+where
+    M: EntityTrait,
+{
+    Ok(get_columns::<M>(args, "variant")?.into_iter().map(|(col, _)| col).collect())
+}
+
+/// Reverse lookup kinds
+#[derive(Clone, Copy, PartialEq)]
+enum Lookup {
+    Unique,
+    Many,
+    Group,
+}
+
+/// Lookup columns facility
+/// Searches between macro arguments for the columns to generate reverse lookups for,
+/// unique ones in the form #[macro(lookup = "column")] or #[macro(lookup("column1", "column2"))],
+/// non-unique ones in the form #[macro(lookup_many = "column")] or #[macro(lookup_many("column1", "column2"))]
+/// and groups in the form #[macro(group = "column")] or #[macro(group("column1", "column2"))]
+fn get_lookups<M>(args: &[NestedMeta]) -> syn::Result<Vec<(M::Column, &LitStr, Lookup)>>
+where
+    M: EntityTrait,
+    <M as EntityTrait>::Column: PartialEq,
+{
+    let mut lookups: Vec<(M::Column, &LitStr, Lookup)> = vec![];
+    for (name, kind) in [("lookup", Lookup::Unique), ("lookup_many", Lookup::Many), ("group", Lookup::Group)] {
+        for (col, s) in get_columns::<M>(args, name)? {
+            if <M as EntityTrait>::PrimaryKey::iter().any(|k| k.into_column() == col) {
+                return Err(syn::Error::new(s.span(), format!("Primary key {col:?} can't be used as {name} column")));
+            }
+            // lookup and lookup_many columns share the same method name
+            if lookups.iter().any(|(other, _, other_kind)| {
+                *other == col && (*other_kind == Lookup::Group) == (kind == Lookup::Group)
+            }) {
+                return Err(syn::Error::new(s.span(), format!("Duplicated {name} column {col:?}")));
+            }
+            lookups.push((col, s, kind));
+        }
+    }
+    Ok(lookups)
+}
+
+/// Extracts integer values, if value is an integer
+fn get_integer(val: &Value, span: Span) -> syn::Result<Option<i64>> {
+    Ok(match val {
+        Value::TinyInt(Some(n)) => Some(i64::from(*n)),
+        Value::SmallInt(Some(n)) => Some(i64::from(*n)),
+        Value::Int(Some(n)) => Some(i64::from(*n)),
+        Value::BigInt(Some(n)) => Some(*n),
+        Value::TinyUnsigned(Some(n)) => Some(i64::from(*n)),
+        Value::SmallUnsigned(Some(n)) => Some(i64::from(*n)),
+        Value::Unsigned(Some(n)) => Some(i64::from(*n)),
+        Value::BigUnsigned(Some(n)) => {
+            Some(i64::try_from(*n).map_err(|_| syn::Error::new(span, format!("Integer value {n} is out of range")))?)
+        }
+        _ => None,
+    })
+}
+
+/// Integer types accepted as enum representation
+const INT_REPRS: &[&str] = &["i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "i128", "u128", "isize", "usize"];
+
+/// Searches for an integer representation between enum attributes, in the form #[repr(u8)]
+fn get_repr(item: &ItemEnum) -> Option<Ident> {
+    item.attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(ml)) => Some(ml.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(p)) => {
+                p.get_ident().filter(|ident| INT_REPRS.iter().any(|repr| *ident == repr)).cloned()
+            }
+            _ => None,
+        })
+}
+
+/// Smallest integer type able to hold all given values
+fn get_int_type(values: impl Iterator<Item = i64>) -> &'static str {
+    let (min, max) = values.fold((0, 0), |(min, max), n| (n.min(min), n.max(max)));
+    if min >= i64::from(i8::MIN) && max <= i64::from(i8::MAX) {
+        "i8"
+    } else if min >= 0 && max <= i64::from(u8::MAX) {
+        "u8"
+    } else if min >= i64::from(i16::MIN) && max <= i64::from(i16::MAX) {
+        "i16"
+    } else if min >= 0 && max <= i64::from(u16::MAX) {
+        "u16"
+    } else if min >= i64::from(i32::MIN) && max <= i64::from(i32::MAX) {
+        "i32"
+    } else if min >= 0 && max <= i64::from(u32::MAX) {
+        "u32"
+    } else {
+        "i64"
+    }
+}
+
+/// Const expression building given date
+#[cfg(feature = "chrono")]
+fn chrono_date(d: &chrono::NaiveDate) -> TokenStream {
+    let (year, month, day) = (d.year(), d.month(), d.day());
+    quote! {
+        match ::chrono::NaiveDate::from_ymd_opt(#year, #month, #day) {
+            Some(d) => d,
+            None => panic!("Invalid date"),
+        }
+    }
+}
+
+/// Const expression building given time
+#[cfg(feature = "chrono")]
+fn chrono_time(t: &chrono::NaiveTime) -> TokenStream {
+    let (hour, minute, second, nano) = (t.hour(), t.minute(), t.second(), t.nanosecond());
+    quote! {
+        match ::chrono::NaiveTime::from_hms_nano_opt(#hour, #minute, #second, #nano) {
+            Some(t) => t,
+            None => panic!("Invalid time"),
+        }
+    }
+}
+
+/// Const expression building given date and time
+#[cfg(feature = "chrono")]
+fn chrono_date_time(dt: &chrono::NaiveDateTime) -> TokenStream {
+    let (d, t) = (chrono_date(&dt.date()), chrono_time(&dt.time()));
+    quote! { ::chrono::NaiveDateTime::new(#d, #t) }
+}
+
+/// Const expression building given decimal
+#[cfg(feature = "rust_decimal")]
+fn decimal(d: &rust_decimal::Decimal) -> TokenStream {
+    let mantissa = d.mantissa().unsigned_abs();
+    let (lo, mid, hi) = (mantissa as u32, (mantissa >> 32) as u32, (mantissa >> 64) as u32);
+    let (negative, scale) = (d.is_sign_negative(), d.scale());
+    quote! { ::rust_decimal::Decimal::from_parts(#lo, #mid, #hi, #negative, #scale) }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::block_on;
+    pub use syn;
+}
+
 /// Replacement types
 enum Replacement {
-    Type(TokenStream),
-    Fn(TokenStream, Option<TokenStream>),
+    Type(Type),
+    Fn(Path, Type),
+    Split(String, Option<Box<Replacement>>),
+    Generate(Ident),
+    ActiveEnum(Type),
+    Skip,
+}
+
+/// Const expression calling given replacement function with given value
+/// Single-segment functions are associated functions of the replacement type, paths are called as they are
+fn call(f: &Path, r: &Type, v: TokenStream) -> TokenStream {
+    if f.leading_colon.is_none() && f.segments.len() == 1 {
+        quote! { <#r>::#f(#v) }
+    } else {
+        quote! { #f(#v) }
+    }
 }
 
 /// Field replacement facility
 /// Searches between macro arguments
-fn get_replacement<M>(col: M::Column, args: &[NestedMeta]) -> Option<Replacement>
+fn get_replacement<M>(col: M::Column, args: &[NestedMeta]) -> syn::Result<Option<Replacement>>
 where
     M: EntityTrait,
     M::Column: PartialEq,
@@ -405,82 +1773,466 @@ where
     let col_name = format!("{col:?}");
     let field_name = col_name.to_snake_case();
     // search for replacements
-    args.iter().find_map(|arg| {
+    for arg in args {
         // simple #[macro(field = "enum")]
         if let NestedMeta::Meta(Meta::NameValue(mv)) = arg {
             if mv.path.is_ident(&col_name) || mv.path.is_ident(&field_name) {
                 if let Lit::Str(s) = &mv.lit {
-                    let ident = Ident::new(&s.value(), Span::call_site());
-                    return Some(Replacement::Type(quote! { #ident }));
+                    return Ok(Some(Replacement::Type(s.parse()?)));
                 }
             }
         }
         // quite complex #[macro(field(type = "enum", fn = "foo"))]
         if let NestedMeta::Meta(Meta::List(ml)) = arg {
             if ml.path.is_ident(&col_name) || ml.path.is_ident(&field_name) {
-                return ml.nested.iter().fold(None, |mut acc, nested| {
+                // opt out #[macro(field(skip))]
+                if ml
+                    .nested
+                    .iter()
+                    .any(|nested| matches!(nested, NestedMeta::Meta(Meta::Path(p)) if p.is_ident("skip")))
+                {
+                    return Ok(Some(Replacement::Skip));
+                }
+                let (mut t, mut f, mut split, mut generate, mut active_enum) = (None, None, None, None, None);
+                for nested in &ml.nested {
                     if let NestedMeta::Meta(Meta::NameValue(mv)) = nested {
                         if let Lit::Str(s) = &mv.lit {
-                            let ident = Ident::new(&s.value(), Span::call_site());
                             if mv.path.is_ident("type") {
-                                if let Some(Replacement::Fn(f, None)) = acc {
-                                    acc = Some(Replacement::Fn(f, Some(quote! { #ident })));
-                                } else {
-                                    acc = Some(Replacement::Type(quote! { #ident }));
-                                }
+                                t = Some(s.parse()?);
                             } else if mv.path.is_ident("fn") {
-                                if let Some(Replacement::Type(t)) = acc {
-                                    acc = Some(Replacement::Fn(quote! { #ident }, Some(t)));
-                                } else {
-                                    acc = Some(Replacement::Fn(quote! { #ident }, None));
+                                f = Some(s.parse()?);
+                            } else if mv.path.is_ident("split") {
+                                if s.value().is_empty() {
+                                    return Err(syn::Error::new(s.span(), "Split separator can't be empty"));
                                 }
+                                split = Some(s.value());
+                            } else if mv.path.is_ident("generate") {
+                                generate = Some(s.parse()?);
+                            } else if mv.path.is_ident("active_enum") {
+                                // only ActiveEnum columns are stored as database enums
+                                if !is_enum(col.def().get_column_type()) {
+                                    return Err(syn::Error::new(
+                                        s.span(),
+                                        format!("Field {col_name} isn't an ActiveEnum, its column type isn't an enum"),
+                                    ));
+                                }
+                                active_enum = Some(s.parse()?);
                             }
                         }
                     }
-                    acc
+                }
+                let replace = match (f, t, generate, active_enum) {
+                    (Some(f), Some(t), None, None) => Some(Replacement::Fn(f, t)),
+                    // teoretically we could accept only a function, but we won't know the return type
+                    (Some(_), None, None, None) => {
+                        return Err(syn::Error::new_spanned(ml, format!("Missing parameter type for field {col_name}")))
+                    }
+                    (None, Some(t), None, None) => Some(Replacement::Type(t)),
+                    // generate #[macro(field(generate = "enum"))] creates the enum from field values
+                    (None, None, Some(r), None) => Some(Replacement::Generate(r)),
+                    // active_enum #[macro(field(active_enum = "enum"))] mirrors the ActiveEnum variants of the field
+                    (None, None, None, Some(r)) if split.is_none() => Some(Replacement::ActiveEnum(r)),
+                    (None, None, None, Some(_)) => {
+                        return Err(syn::Error::new_spanned(ml, format!("ActiveEnum field {col_name} can't be split")))
+                    }
+                    (None, None, None, None) => None,
+                    (_, _, Some(_), _) => {
+                        return Err(syn::Error::new_spanned(
+                            ml,
+                            format!("Field {col_name} can't both generate an enum and use type or fn parameters"),
+                        ))
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            ml,
+                            format!("Field {col_name} can't both mirror an ActiveEnum and use type or fn parameters"),
+                        ))
+                    }
+                };
+                // split #[macro(field(type = "enum", split = " / "))] applies replacement to every piece
+                return Ok(match split {
+                    Some(sep) => Some(Replacement::Split(sep, replace.map(Box::new))),
+                    None => replace,
                 });
             }
         }
-        None
-    })
+    }
+    Ok(None)
+}
+
+/// Tells if given column type is a database enum, or an array of them
+fn is_enum(t: &ColumnType) -> bool {
+    match t {
+        ColumnType::Enum { .. } => true,
+        ColumnType::Array(t) => is_enum(t),
+        _ => false,
+    }
 }
 
-/// Data retrieve function with cache capabilities
-/// File access is sync to not have to depend on an async runtime
-async fn get_data<M, F, Fut>(get_conn: F) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+/// Value of given field, ActiveEnum fields are read as the name of their Rust variant
+fn get_field<M>(
+    model: &M::Model,
+    col: M::Column,
+    replace: Option<&Replacement>,
+    row: &str,
+    span: Span,
+) -> syn::Result<Value>
 where
-    M: EntityTrait + EntityFilter + Default,
-    <M as EntityTrait>::Model: Serialize + DeserializeOwned,
-    F: Fn() -> Fut,
-    Fut: Future<Output = syn::Result<DatabaseConnection>>,
+    M: EntityTrait,
+    M::Model: Serialize,
 {
-    let instance = M::default();
-    let mut cache = env::temp_dir();
-    cache.push(EntityName::table_name(&instance));
-    cache.set_extension("cache");
-    if cache.exists() {
-        info!("Cache file {} exists, loading data from there", cache.display());
-
-        let file = fs::File::open(&cache)
-            .map_err(|e| syn::Error::new(Span::call_site(), format!("Error reading {}: {}", cache.display(), e)))?;
+    if !matches!(replace, Some(Replacement::ActiveEnum(_))) {
+        return Ok(model.get(col));
+    }
+    // Model values hold database values, while serialized unit variants are their Rust names
+    let error = || syn::Error::new(span, format!("Can't read ActiveEnum variant of field {col:?} of row {row}"));
+    let mut fields = match serde_json::to_value(model) {
+        Ok(JsonValue::Object(fields)) => fields,
+        _ => return Err(error()),
+    };
+    fields.remove(&format!("{col:?}").to_snake_case()).and_then(json_variant).ok_or_else(error)
+}
 
-        match bincode::deserialize_from(io::BufReader::new(file)) {
-            Ok(data) => return Ok(data),
-            Err(e) => error!("Error deserializing {}: {}", cache.display(), e),
+/// Maps a serialized ActiveEnum value, optional or in an array, to variant names
+fn json_variant(value: JsonValue) -> Option<Value> {
+    match value {
+        JsonValue::Null => Some(Value::String(None)),
+        JsonValue::String(s) => syn::parse_str::<Ident>(&s).ok().map(|_| Value::String(Some(Box::new(s)))),
+        #[cfg(feature = "postgres-array")]
+        JsonValue::Array(values) => {
+            let values = values
+                .into_iter()
+                .map(|v| json_variant(v).filter(|v| *v != Value::String(None)))
+                .collect::<Option<Vec<_>>>()?;
+            Some(Value::Array(ArrayType::String, Some(Box::new(values))))
         }
-    } else {
-        info!("Cache file {} doesn't exists, creating", cache.display());
-    }
-
-    let conn = get_conn().await?;
-    let data = <M as EntityTrait>::find()
-        .filter(M::filter())
-        .all(&conn)
-        .await
-        .map_err(|e| syn::Error::new(Span::call_site(), e))?;
-    let buf = bincode::serialize(&data)
-        .map_err(|e| syn::Error::new(Span::call_site(), format!("Error serializing {}: {}", cache.display(), e)))?;
-    fs::write(&cache, buf)
-        .map_err(|e| syn::Error::new(Span::call_site(), format!("Error writing {}: {}", cache.display(), e)))?;
-    Ok(data)
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use sea_orm::Value;
+
+    use syn::{parse_quote, ItemEnum, NestedMeta};
+
+    use crate::{
+        json, json_variant,
+        macros::block_on,
+        symbols,
+        test_model::{Entity, Model},
+        type_name, Memory,
+    };
+
+    fn games(names: &[&str]) -> Memory {
+        Memory::new(
+            names.iter().map(|name| Model { name: String::from(*name), rank: 1, series: None }).collect::<Vec<_>>(),
+        )
+    }
+
+    fn variants(item: &ItemEnum) -> Vec<String> {
+        item.variants.iter().map(|variant| variant.ident.to_string()).collect()
+    }
+
+    fn drinks() -> Memory {
+        use crate::test_model::drinks::{Model, Tea};
+
+        Memory::new(vec![
+            Model { name: String::from("Morning"), kind: Tea::BreakfastTea, fallback: None },
+            Model { name: String::from("Afternoon"), kind: Tea::EverydayTea, fallback: Some(Tea::BreakfastTea) },
+        ])
+    }
+
+    #[test]
+    fn maps_active_enum_variants() {
+        use crate::test_model::drinks::Entity;
+
+        let mut item: ItemEnum = parse_quote! { enum Drink {} };
+        let args: Vec<NestedMeta> = vec![
+            parse_quote! { table = "drinks" },
+            parse_quote! { kind(active_enum = "Tea") },
+            parse_quote! { fallback(active_enum = "Tea") },
+        ];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, drinks())).unwrap().to_string();
+        let kind = quote! {
+            pub const fn kind(&self) -> Tea {
+                match self {
+                    Drink::Morning => <Tea>::BreakfastTea,
+                    Drink::Afternoon => <Tea>::EverydayTea,
+                }
+            }
+        };
+        assert!(tokens.contains(&kind.to_string()), "{tokens}");
+        let fallback = quote! { Some(match self { Drink::Afternoon => <Tea>::BreakfastTea, _ => return None, }) };
+        assert!(tokens.contains(&fallback.to_string()), "{tokens}");
+    }
+
+    #[test]
+    fn keeps_active_enum_values_by_default() {
+        use crate::test_model::drinks::Entity;
+
+        let mut item: ItemEnum = parse_quote! { enum Drink {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "drinks" }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, drinks())).unwrap().to_string();
+        let kind = quote! {
+            pub const fn kind(&self) -> &'static str {
+                match self {
+                    Drink::Morning => "B",
+                    Drink::Afternoon => "E",
+                }
+            }
+        };
+        assert!(tokens.contains(&kind.to_string()), "{tokens}");
+    }
+
+    #[test]
+    fn rejects_active_enum_on_other_columns() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> =
+            vec![parse_quote! { table = "games" }, parse_quote! { series(active_enum = "Tea") }];
+        let e = block_on(symbols::<Entity, _>(&mut item, &args, games(&["Tetris"]))).unwrap_err();
+        assert_eq!(e.to_string(), "Field Series isn't an ActiveEnum, its column type isn't an enum");
+    }
+
+    #[test]
+    fn reads_serialized_variants() {
+        let variant = |s: &str| Value::String(Some(Box::new(String::from(s))));
+        assert_eq!(json_variant(serde_json::json!("EverydayTea")), Some(variant("EverydayTea")));
+        assert_eq!(json_variant(serde_json::json!(null)), Some(Value::String(None)));
+        assert_eq!(json_variant(serde_json::json!("everyday tea")), None);
+        assert_eq!(json_variant(serde_json::json!(1)), None);
+    }
+
+    #[test]
+    fn sorts_json_object_keys() {
+        let tokens = json(&serde_json::json!({ "b": 1, "a": [true, null] }));
+        let expected = quote! {
+            ::symbols_models::Json::Object(&[
+                ("a", ::symbols_models::Json::Array(&[
+                    ::symbols_models::Json::Bool(true),
+                    ::symbols_models::Json::Null
+                ])),
+                ("b", ::symbols_models::Json::Int(1i64))
+            ])
+        };
+        assert_eq!(tokens.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn tracks_fixture_files() {
+        let path = std::env::temp_dir().join("symbols_tracks_fixture_files.json");
+        std::fs::write(&path, r#"[{ "name": "Minecraft", "rank": 1, "series": null }]"#).unwrap();
+        let fixture = path.to_str().unwrap();
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }, parse_quote! { fixture = #fixture }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, games(&[]))).unwrap();
+        assert_eq!(variants(&item), ["Minecraft"]);
+        let tracked = quote! { const _: &[u8] = include_bytes!(#fixture); };
+        assert!(tokens.to_string().contains(&tracked.to_string()), "{tokens}");
+    }
+
+    #[test]
+    fn reports_variant_collisions() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }];
+        let source = games(&["Wii Fit / Plus", "Wii Fit Plus"]);
+        let e = block_on(symbols::<Entity, _>(&mut item, &args, source)).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Variant name WiiFitPlus collides between row \"Wii Fit / Plus\" and row \"Wii Fit Plus\", \
+            use #[macro(on_collision = \"suffix\")] to add a numeric suffix \
+            or #[macro(rename(Variant = \"Wii Fit Plus\"))] to pick a name"
+        );
+    }
+
+    #[test]
+    fn reports_collisions_with_declared_variants() {
+        let mut item: ItemEnum = parse_quote! { enum Game { WiiFitPlus(u8) } };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }];
+        let e = block_on(symbols::<Entity, _>(&mut item, &args, games(&["Wii Fit Plus"]))).unwrap_err();
+        assert!(e.to_string().starts_with("Variant name WiiFitPlus collides between declared variant and row"));
+    }
+
+    #[test]
+    fn suffixes_variant_collisions() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }, parse_quote! { on_collision = "suffix" }];
+        let source = games(&["Wii Fit / Plus", "Wii Fit Plus", "Wii-Fit-Plus"]);
+        block_on(symbols::<Entity, _>(&mut item, &args, source)).unwrap();
+        assert_eq!(variants(&item), ["WiiFitPlus", "WiiFitPlus2", "WiiFitPlus3"]);
+    }
+
+    #[test]
+    fn renames_colliding_variants() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> =
+            vec![parse_quote! { table = "games" }, parse_quote! { rename(WiiFitPlusToo = "Wii Fit Plus") }];
+        let source = games(&["Wii Fit / Plus", "Wii Fit Plus"]);
+        block_on(symbols::<Entity, _>(&mut item, &args, source)).unwrap();
+        assert_eq!(variants(&item), ["WiiFitPlus", "WiiFitPlusToo"]);
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn keeps_decimal_scale() {
+        use std::str::FromStr;
+
+        let d = rust_decimal::Decimal::from_str("12.50").unwrap();
+        let (_, value) = crate::get_value(
+            &"price",
+            Value::Decimal(Some(Box::new(d))),
+            None,
+            "row",
+            "_",
+            proc_macro2::Span::call_site(),
+        )
+        .unwrap()
+        .unwrap();
+        let expected = quote! { ::rust_decimal::Decimal::from_parts(1250u32, 0u32, 0u32, false, 2u32) };
+        assert_eq!(value.unwrap().to_string(), expected.to_string());
+        assert_eq!(rust_decimal::Decimal::from_parts(1250, 0, 0, false, 2).to_string(), "12.50");
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn rejects_out_of_range_big_decimals() {
+        use std::str::FromStr;
+
+        let d = sea_orm::prelude::BigDecimal::from_str("1e40").unwrap();
+        let e = crate::get_value(
+            &"price",
+            Value::BigDecimal(Some(Box::new(d))),
+            None,
+            "row",
+            "_",
+            proc_macro2::Span::call_site(),
+        )
+        .unwrap_err();
+        assert!(e.to_string().starts_with("Invalid decimal "), "{e}");
+        assert!(e.to_string().contains(r#"for field "price" of row row"#), "{e}");
+    }
+
+    #[cfg(feature = "postgres-array")]
+    fn releases() -> Memory {
+        use crate::test_model::releases::Model;
+
+        Memory::new(vec![
+            Model {
+                name: String::from("Tetris"),
+                platforms: vec![String::from("Game Boy"), String::from("NES")],
+                years: vec![1984, 1989],
+            },
+            Model { name: String::from("Zelda"), platforms: vec![String::from("NES")], years: vec![] },
+        ])
+    }
+
+    #[cfg(feature = "postgres-array")]
+    #[test]
+    fn maps_array_elements() {
+        use crate::test_model::releases::Entity;
+
+        let mut item: ItemEnum = parse_quote! { enum Release {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "releases" }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, releases())).unwrap().to_string();
+        let years = quote! { Release::Tetris => { const VALUES: &[i16] = &[1984, 1989]; VALUES } };
+        assert!(tokens.contains(&years.to_string()), "{tokens}");
+        let platforms = quote! { pub const fn platforms(&self) -> &'static [&'static str] };
+        assert!(tokens.contains(&platforms.to_string()), "{tokens}");
+        let tetris = quote! { Release::Tetris => { const VALUES: &[&'static str] = &["Game Boy", "NES"]; VALUES } };
+        assert!(tokens.contains(&tetris.to_string()), "{tokens}");
+    }
+
+    #[cfg(feature = "postgres-array")]
+    #[test]
+    fn replaces_array_elements() {
+        use crate::test_model::releases::Entity;
+
+        let mut item: ItemEnum = parse_quote! { enum Release {} };
+        let args: Vec<NestedMeta> =
+            vec![parse_quote! { table = "releases" }, parse_quote! { platforms(generate = "Platform") }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, releases())).unwrap().to_string();
+        let platform = quote! { enum Platform { GameBoy, Nes } };
+        assert!(tokens.contains(&platform.to_string()), "{tokens}");
+        let platforms = quote! { pub const fn platforms(&self) -> &'static [Platform] };
+        assert!(tokens.contains(&platforms.to_string()), "{tokens}");
+        let tetris = quote! {
+            Release::Tetris => { const VALUES: &[Platform] = &[<Platform>::GameBoy, <Platform>::Nes]; VALUES }
+        };
+        assert!(tokens.contains(&tetris.to_string()), "{tokens}");
+    }
+
+    #[cfg(feature = "postgres-array")]
+    #[test]
+    fn rejects_null_array_elements() {
+        use sea_orm::sea_query::ArrayType;
+
+        let value = Value::Array(ArrayType::String, Some(Box::new(vec![Value::String(None)])));
+        let e = crate::get_value(&"platforms", value, None, "Tetris", "_", proc_macro2::Span::call_site()).unwrap_err();
+        assert_eq!(e.to_string(), r#"Unsupported NULL array element for field "platforms" of row Tetris"#);
+    }
+
+    #[test]
+    fn replaces_non_string_fields_with_fn() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> =
+            vec![parse_quote! { table = "games" }, parse_quote! { rank(type = "Rank", fn = "new") }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, games(&["Tetris"]))).unwrap().to_string();
+        let rank = quote! { pub const fn rank(&self) -> Rank { match self { Game::Tetris => <Rank>::new(1), } } };
+        assert!(tokens.contains(&rank.to_string()), "{tokens}");
+
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> =
+            vec![parse_quote! { table = "games" }, parse_quote! { rank(type = "u8", fn = "ranks::unsigned") }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, games(&["Tetris"]))).unwrap().to_string();
+        let rank = quote! { pub const fn rank(&self) -> u8 { match self { Game::Tetris => ranks::unsigned(1), } } };
+        assert!(tokens.contains(&rank.to_string()), "{tokens}");
+    }
+
+    #[test]
+    fn names_types_without_token_spacing() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }, parse_quote! { rank(type = "Rank") }];
+        let e = block_on(symbols::<Entity, _>(&mut item, &args, games(&["Tetris"]))).unwrap_err();
+        assert_eq!(e.to_string(), "Field Rank of type i8 can only be replaced using both type and fn parameters");
+        assert_eq!(type_name(&quote! { ::rust_decimal::Decimal }), "::rust_decimal::Decimal");
+        assert_eq!(type_name(&quote! { ::chrono::DateTime<::chrono::Utc> }), "::chrono::DateTime<::chrono::Utc>");
+        assert_eq!(type_name(&quote! { &'static [u8] }), "&'static [u8]");
+        assert_eq!(type_name(&quote! { (i8, &'static str) }), "(i8, &'static str)");
+    }
+
+    #[test]
+    fn suggests_unknown_arguments() {
+        let error = |arg: NestedMeta| {
+            let mut item: ItemEnum = parse_quote! { enum Game {} };
+            let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }, arg];
+            block_on(symbols::<Entity, _>(&mut item, &args, games(&["Tetris"]))).unwrap_err().to_string()
+        };
+        assert_eq!(
+            error(parse_quote! { on_colision = "suffix" }),
+            "Unknown argument on_colision, did you mean on_collision?"
+        );
+        assert_eq!(error(parse_quote! { sereis = "Series" }), "Unknown argument sereis, did you mean series?");
+        assert_eq!(error(parse_quote! { rank(tpye = "Rank") }), "Unknown argument tpye, did you mean type?");
+        assert_eq!(error(parse_quote! { rank(sklp) }), "Unknown argument sklp, did you mean skip?");
+        assert_eq!(error(parse_quote! { colour = "Red" }), "Unknown argument colour");
+    }
+
+    #[test]
+    fn keeps_lookups_without_rows() {
+        let mut item: ItemEnum = parse_quote! { enum Game {} };
+        let args: Vec<NestedMeta> = vec![
+            parse_quote! { table = "games" },
+            parse_quote! { lookup = "rank" },
+            parse_quote! { group = "series" },
+            parse_quote! { series(generate = "Series") },
+        ];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, games(&[]))).unwrap().to_string();
+        let rank = quote! { pub const fn get_by_rank(rank: i8) -> Option<Self> { match rank { _ => None, } } };
+        assert!(tokens.contains(&rank.to_string()), "{tokens}");
+        let series =
+            quote! { pub const fn all_with_series(series: Series) -> &'static [Self] { match series { _ => &[], } } };
+        assert!(tokens.contains(&series.to_string()), "{tokens}");
+    }
 }
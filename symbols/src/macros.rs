@@ -0,0 +1,82 @@
+//! Ready-made proc-macro entry point
+
+use std::{env, future::Future, time::Duration};
+
+use proc_macro2::Span;
+
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+
+use syn::{LitStr, NestedMeta};
+
+use crate::get_str_arg;
+
+/// Declares a proc-macro attribute that populates enums from the given entities
+/// Every entity is registered under its table name, the table is then picked using the `table` macro argument.
+/// Data is retrieved from the database pointed by `DATABASE_URL` environment variable, with a [Cache](crate::Cache) layer.
+///
+/// Must be invoked at the root of a `proc-macro = true` crate:
+/// ```ignore
+/// symbols::declare_symbols_macro! {
+///     /// Populates an enum with data from the given table
+///     pub fn example {
+///         "best_selling_video_games" => model::Entity,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_symbols_macro {
+    (
+        $(#[$meta:meta])*
+        $vis:vis fn $name:ident {
+            $($table:literal => $entity:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[proc_macro_attribute]
+        $vis fn $name(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+            let mut item = $crate::__private::syn::parse_macro_input!(input as $crate::__private::syn::ItemEnum);
+            let args = $crate::__private::syn::parse_macro_input!(args as $crate::__private::syn::AttributeArgs);
+
+            $crate::__private::block_on(async {
+                let table = $crate::get_table(&args)?;
+                match table.value().as_str() {
+                    $($table => {
+                        $crate::symbols::<$entity, _>(&mut item, &args, $crate::Cache::new($crate::get_conn)).await
+                    },)+
+                    other => Err($crate::__private::syn::Error::new(
+                        table.span(),
+                        format!("Unrecognized table \"{}\"", other),
+                    )),
+                }
+            })
+            .unwrap_or_else($crate::__private::syn::Error::into_compile_error)
+            .into()
+        }
+    };
+}
+
+/// Searches for the table name between macro arguments, in the form #[macro(table = "table_name")]
+pub fn get_table(args: &[NestedMeta]) -> syn::Result<LitStr> {
+    get_str_arg(args, "table").cloned().ok_or_else(|| syn::Error::new(Span::call_site(), "Missing table attribute"))
+}
+
+/// Opens a single-connection pool to the database pointed by `DATABASE_URL` environment variable
+pub async fn get_conn() -> syn::Result<DatabaseConnection> {
+    let url = env::var("DATABASE_URL").map_err(|e| syn::Error::new(Span::call_site(), e))?;
+    let mut options = ConnectOptions::new(url);
+    options
+        .min_connections(1)
+        .max_connections(1)
+        .connect_timeout(Duration::from_secs(1))
+        .idle_timeout(Duration::from_secs(1));
+    Database::connect(options).await.map_err(|e| syn::Error::new(Span::call_site(), e))
+}
+
+/// Runs given future on a current-thread async runtime, to be able to use sea-orm inside a proc-macro
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build async runtime")
+        .block_on(future)
+}
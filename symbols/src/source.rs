@@ -0,0 +1,162 @@
+//! Data sources the enums are populated from
+
+use std::{any::Any, env, fs, future::Future, io};
+
+use proc_macro2::Span;
+
+use sea_orm::{DatabaseConnection, EntityName, EntityTrait, ModelTrait, QueryFilter};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use symbols_models::EntityFilter;
+
+use tracing::{error, info};
+
+/// Source of rows for an entity
+///
+/// Implemented for:
+/// * [DatabaseConnection], that queries the entity table applying [EntityFilter]
+/// * every `Fn() -> impl Future<Output = syn::Result<impl DataSource>>`, that lazily builds the source on first fetch
+/// * [Cache], that stores data retrieved from another source in temp folder
+/// * [Memory], that holds rows in memory
+/// * [Fixture](crate::Fixture), that loads rows from a file
+pub trait DataSource {
+    /// Retrieves all rows for given entity
+    fn fetch<M>(&self) -> impl Future<Output = syn::Result<Vec<<M as EntityTrait>::Model>>>
+    where
+        M: EntityTrait + EntityFilter + Default,
+        <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static;
+}
+
+impl DataSource for DatabaseConnection {
+    async fn fetch<M>(&self) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+    where
+        M: EntityTrait + EntityFilter + Default,
+        <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static,
+    {
+        <M as EntityTrait>::find()
+            .filter(M::filter())
+            .all(self)
+            .await
+            .map_err(|e| syn::Error::new(Span::call_site(), e))
+    }
+}
+
+impl<F, Fut, S> DataSource for F
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = syn::Result<S>>,
+    S: DataSource,
+{
+    async fn fetch<M>(&self) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+    where
+        M: EntityTrait + EntityFilter + Default,
+        <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static,
+    {
+        self().await?.fetch::<M>().await
+    }
+}
+
+/// Cache layer over another data source
+/// Data is stored in temp folder under the name of `<table>.cache`, inner source is used only when the file is missing.
+/// File access is sync to not have to depend on an async runtime
+pub struct Cache<S> {
+    source: S,
+}
+
+impl<S> Cache<S> {
+    /// Wraps given source with a cache layer
+    pub fn new(source: S) -> Self {
+        Cache { source }
+    }
+}
+
+impl<S: DataSource> DataSource for Cache<S> {
+    async fn fetch<M>(&self) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+    where
+        M: EntityTrait + EntityFilter + Default,
+        <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static,
+    {
+        let instance = M::default();
+        let mut cache = env::temp_dir();
+        cache.push(EntityName::table_name(&instance));
+        cache.set_extension("cache");
+        if cache.exists() {
+            info!("Cache file {} exists, loading data from there", cache.display());
+
+            let file = fs::File::open(&cache)
+                .map_err(|e| syn::Error::new(Span::call_site(), format!("Error reading {}: {}", cache.display(), e)))?;
+
+            match bincode::deserialize_from(io::BufReader::new(file)) {
+                Ok(data) => return Ok(data),
+                Err(e) => error!("Error deserializing {}: {}", cache.display(), e),
+            }
+        } else {
+            info!("Cache file {} doesn't exists, creating", cache.display());
+        }
+
+        let data = self.source.fetch::<M>().await?;
+        let buf = bincode::serialize(&data)
+            .map_err(|e| syn::Error::new(Span::call_site(), format!("Error serializing {}: {}", cache.display(), e)))?;
+        fs::write(&cache, buf)
+            .map_err(|e| syn::Error::new(Span::call_site(), format!("Error writing {}: {}", cache.display(), e)))?;
+        Ok(data)
+    }
+}
+
+/// In-memory rows, mostly useful for tests
+pub struct Memory {
+    rows: Box<dyn Any>,
+}
+
+impl Memory {
+    /// Holds given rows, they will be returned only when fetching the entity they belong to
+    pub fn new<T: ModelTrait + Clone + 'static>(rows: Vec<T>) -> Self {
+        Memory { rows: Box::new(rows) }
+    }
+}
+
+impl DataSource for Memory {
+    async fn fetch<M>(&self) -> syn::Result<Vec<<M as EntityTrait>::Model>>
+    where
+        M: EntityTrait + EntityFilter + Default,
+        <M as EntityTrait>::Model: Serialize + DeserializeOwned + 'static,
+    {
+        self.rows.downcast_ref::<Vec<<M as EntityTrait>::Model>>().cloned().ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                format!("In-memory rows don't belong to table {}", EntityName::table_name(&M::default())),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, ItemEnum, NestedMeta};
+
+    use super::Memory;
+
+    use crate::{
+        macros::block_on,
+        symbols,
+        test_model::{Entity, Model},
+    };
+
+    #[test]
+    fn populates_from_memory() {
+        let rows = vec![
+            Model { name: String::from("Tetris (EA)"), rank: 3, series: Some(String::from("Tetris")) },
+            Model { name: String::from("Minecraft"), rank: 1, series: None },
+        ];
+        let mut item: ItemEnum = parse_quote! { pub enum Game {} };
+        let args: Vec<NestedMeta> = vec![parse_quote! { table = "games" }];
+        let tokens = block_on(symbols::<Entity, _>(&mut item, &args, Memory::new(rows))).unwrap().to_string();
+
+        let variants = item.variants.iter().map(|variant| variant.ident.to_string()).collect::<Vec<_>>();
+        assert_eq!(variants, ["TetrisEa", "Minecraft"]);
+        assert!(tokens.contains("Game :: TetrisEa => \"Tetris (EA)\""), "{tokens}");
+        assert!(tokens.contains("pub const fn rank (& self) -> i8"), "{tokens}");
+        assert!(tokens.contains("pub const fn series (& self) -> Option < & 'static str >"), "{tokens}");
+    }
+}
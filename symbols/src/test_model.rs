@@ -0,0 +1,101 @@
+//! Entity used by unit tests
+
+use sea_orm::entity::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use symbols_models::EntityFilter;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "games")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub name: String,
+    pub rank: i8,
+    pub series: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl EntityFilter for Entity {}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        (*self as isize) == (*other as isize)
+    }
+}
+
+/// Entity with Postgres array fields
+#[cfg(feature = "postgres-array")]
+pub mod releases {
+    use sea_orm::entity::prelude::*;
+
+    use serde::{Deserialize, Serialize};
+
+    use symbols_models::EntityFilter;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+    #[sea_orm(table_name = "releases")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub name: String,
+        pub platforms: Vec<String>,
+        pub years: Vec<i16>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    impl EntityFilter for Entity {}
+
+    impl PartialEq for Column {
+        fn eq(&self, other: &Self) -> bool {
+            (*self as isize) == (*other as isize)
+        }
+    }
+}
+
+/// Entity with an ActiveEnum field
+pub mod drinks {
+    use sea_orm::entity::prelude::*;
+
+    use serde::{Deserialize, Serialize};
+
+    use symbols_models::EntityFilter;
+
+    #[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+    #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tea_kind")]
+    pub enum Tea {
+        #[sea_orm(string_value = "E")]
+        EverydayTea,
+        #[sea_orm(string_value = "B")]
+        BreakfastTea,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+    #[sea_orm(table_name = "drinks")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub name: String,
+        pub kind: Tea,
+        pub fallback: Option<Tea>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    impl EntityFilter for Entity {}
+
+    impl PartialEq for Column {
+        fn eq(&self, other: &Self) -> bool {
+            (*self as isize) == (*other as isize)
+        }
+    }
+}